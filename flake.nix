@@ -65,7 +65,14 @@
 
         devShells.default = craneLib.devShell {
           checks = self.checks.${system};
-          packages = [ ];
+          # Shells whose init scripts the integration tests parse
+          packages = with pkgs; [
+            zsh
+            fish
+            powershell
+            elvish
+            nushell
+          ];
         };
       }
     )
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::cli::Commands;
+use crate::cli::{Commands, ConfigCommands, TrashCommands};
 use crate::commands;
 use crate::error::Result;
 use crate::git::runner::GitRunner;
@@ -9,10 +9,61 @@ pub fn dispatch(command: Commands, dry_run: bool, verbose: bool, no_color: bool)
     let cwd = std::env::current_dir()?;
 
     match command {
-        Commands::Init { force } => {
-            let repo_root = GitRunner::find_repo_root(&cwd)?;
-            commands::init::exec(&repo_root, force, no_color)
+        Commands::Init {
+            force,
+            template,
+            yes,
+            exclude_mode,
+        } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::init::exec(
+                &git,
+                commands::init::InitArgs {
+                    force,
+                    template,
+                    yes,
+                    exclude_mode,
+                },
+                no_color,
+            )
+        }
+        Commands::Deinit {
+            remove_worktrees,
+            force,
+            yes,
+        } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::deinit::exec(
+                &git,
+                commands::deinit::DeinitArgs {
+                    remove_worktrees,
+                    force,
+                    yes,
+                },
+                no_color,
+            )
         }
+        Commands::Clone {
+            url,
+            dir,
+            bare,
+            config,
+            no_hooks,
+            exclude_mode,
+        } => commands::clone::exec(
+            &cwd,
+            commands::clone::CloneArgs {
+                url,
+                dir,
+                bare,
+                config,
+                no_hooks,
+                exclude_mode,
+            },
+            dry_run,
+            verbose,
+            no_color,
+        ),
         Commands::Add {
             branch,
             create,
@@ -21,6 +72,11 @@ pub fn dispatch(command: Commands, dry_run: bool, verbose: bool, no_color: bool)
             name,
             detach,
             no_hooks,
+            cd,
+            profile,
+            from_title,
+            issue,
+            kind,
         } => {
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
             commands::add::exec(
@@ -33,6 +89,11 @@ pub fn dispatch(command: Commands, dry_run: bool, verbose: bool, no_color: bool)
                     name,
                     detach,
                     no_hooks,
+                    cd,
+                    profile,
+                    from_title,
+                    issue,
+                    kind,
                 },
                 no_color,
             )
@@ -41,9 +102,54 @@ pub fn dispatch(command: Commands, dry_run: bool, verbose: bool, no_color: bool)
             worktree,
             with_branch,
             force,
+            discard_unpushed,
+            trash,
         } => {
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
-            commands::remove::exec(&git, &worktree, with_branch, force, no_color)
+            commands::remove::exec(
+                &git,
+                commands::remove::RemoveArgs {
+                    worktree,
+                    with_branch,
+                    force,
+                    discard_unpushed,
+                    trash,
+                },
+                no_color,
+            )
+        }
+        Commands::Restore { name } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::trash::exec_restore(&git, &name, no_color)
+        }
+        Commands::Trash { command } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            match command {
+                TrashCommands::List => commands::trash::exec_list(&git, no_color),
+                TrashCommands::Purge { older_than } => {
+                    commands::trash::exec_purge(&git, older_than.as_deref(), no_color)
+                }
+            }
+        }
+        // The schema is the same everywhere, so no repository is needed
+        Commands::Config {
+            command: ConfigCommands::Schema,
+        } => commands::config::exec_schema(),
+        Commands::Config { command } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            match command {
+                ConfigCommands::Get { key } => commands::config::exec_get(&git, &key),
+                ConfigCommands::Set { key, value } => {
+                    commands::config::exec_set(&git, &key, &value, no_color)
+                }
+                ConfigCommands::Edit => commands::config::exec_edit(&git, no_color),
+                ConfigCommands::Validate => commands::config::exec_validate(&git, no_color),
+                ConfigCommands::Show { branch } => {
+                    commands::config::exec_show(&git, branch.as_deref())
+                }
+                ConfigCommands::Migrate => commands::config::exec_migrate(&git, no_color),
+                ConfigCommands::Schema => unreachable!("handled above"),
+            }
         }
         Commands::List {
             porcelain,
@@ -57,6 +163,10 @@ pub fn dispatch(command: Commands, dry_run: bool, verbose: bool, no_color: bool)
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
             commands::cd::exec(&git, &worktree)
         }
+        Commands::Switch { worktree } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::switch::exec(&git, &worktree, no_color)
+        }
         Commands::Status { worktree } => {
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
             commands::status::exec(&git, worktree.as_deref(), no_color)
@@ -67,9 +177,62 @@ pub fn dispatch(command: Commands, dry_run: bool, verbose: bool, no_color: bool)
             with_branch,
             interactive,
             yes,
+            discard_unpushed,
         } => {
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
-            commands::prune::exec(&git, merged, stale, with_branch, interactive, yes, no_color)
+            commands::prune::exec(
+                &git,
+                commands::prune::PruneArgs {
+                    merged,
+                    stale,
+                    with_branch,
+                    interactive,
+                    yes,
+                    discard_unpushed,
+                },
+                no_color,
+            )
+        }
+        Commands::Sync {
+            worktrees,
+            all,
+            rebase,
+            no_fetch,
+            jobs,
+        } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::sync::exec(
+                &git,
+                commands::sync::SyncArgs {
+                    worktrees,
+                    all,
+                    rebase,
+                    no_fetch,
+                    jobs,
+                },
+                no_color,
+            )
+        }
+        Commands::Exec {
+            filter,
+            parallel,
+            output,
+            fail_fast,
+            keep_going: _,
+            command,
+        } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::exec::exec(
+                &git,
+                commands::exec::ExecArgs {
+                    command,
+                    filter,
+                    parallel,
+                    output,
+                    fail_fast,
+                },
+                no_color,
+            )
         }
         Commands::Rename { old, new } => {
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
@@ -77,7 +240,28 @@ pub fn dispatch(command: Commands, dry_run: bool, verbose: bool, no_color: bool)
         }
         Commands::Move { worktree, new_path } => {
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
-            commands::move_wt::exec(&git, &worktree, &new_path, no_color)
+            commands::move_wt::exec(&git, &cwd, &worktree, &new_path, no_color)
+        }
+        Commands::Adopt {
+            path,
+            all: _,
+            run_hooks,
+            yes,
+        } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::adopt::exec(
+                &git,
+                commands::adopt::AdoptArgs {
+                    path,
+                    run_hooks,
+                    yes,
+                },
+                no_color,
+            )
+        }
+        Commands::Undo { yes } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::undo::exec(&git, yes, no_color)
         }
         Commands::Lock { worktree, reason } => {
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
@@ -87,12 +271,18 @@ pub fn dispatch(command: Commands, dry_run: bool, verbose: bool, no_color: bool)
             let git = make_git(&cwd, dry_run, verbose, no_color)?;
             commands::lock::exec_unlock(&git, &worktree, no_color)
         }
+        Commands::Doctor { fix } => {
+            let git = make_git(&cwd, dry_run, verbose, no_color)?;
+            commands::doctor::exec(&git, fix, no_color)
+        }
         Commands::Completion { shell } => commands::completion::exec(shell),
-        Commands::ShellInit { shell } => commands::shell_init::exec(shell),
+        Commands::Prompt { format, no_status } => {
+            commands::prompt::exec(&cwd, format.as_deref(), !no_status)
+        }
+        Commands::ShellInit { shell, prompt } => commands::shell_init::exec(shell, prompt),
     }
 }
 
 fn make_git(cwd: &Path, dry_run: bool, verbose: bool, no_color: bool) -> Result<GitRunner> {
-    let repo_root = GitRunner::find_repo_root(cwd)?;
-    Ok(GitRunner::new(repo_root, dry_run, verbose, no_color))
+    GitRunner::discover(cwd, dry_run, verbose, no_color)
 }
@@ -1,8 +1,5 @@
 use std::process;
 
 fn main() {
-    if let Err(e) = bonsai::run() {
-        eprintln!("Error: {e}");
-        process::exit(1);
-    }
+    process::exit(bonsai::run());
 }
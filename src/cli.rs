@@ -1,5 +1,8 @@
-use clap::{Parser, Subcommand};
-use clap_complete::Shell;
+use clap::{Parser, Subcommand, ValueEnum, ValueHint};
+use clap_complete::{ArgValueCandidates, Shell};
+
+use crate::complete;
+use crate::error::ErrorFormat;
 
 #[derive(Parser)]
 #[command(name = "bonsai", version, about = "Intuitive Git worktree management")]
@@ -18,6 +21,10 @@ pub struct Cli {
     /// Disable colored output
     #[arg(long, global = true)]
     pub no_color: bool,
+
+    /// How to print errors on stderr
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    pub error_format: ErrorFormat,
 }
 
 #[derive(Subcommand)]
@@ -27,23 +34,80 @@ pub enum Commands {
         /// Overwrite existing configuration
         #[arg(long)]
         force: bool,
+
+        /// Start from a template: a path, or a name in ~/.config/bonsai/templates
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Add the suggested hooks without asking
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Where to ignore the worktree directory
+        #[arg(long, value_enum, default_value_t = ExcludeMode::Gitignore)]
+        exclude_mode: ExcludeMode,
+    },
+
+    /// Remove bonsai from the repository, undoing `init`
+    Deinit {
+        /// Also remove the worktrees in the worktree directory
+        #[arg(long)]
+        remove_worktrees: bool,
+
+        /// Remove worktrees even if they have changes that would be lost
+        #[arg(long)]
+        force: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Clone a repository and initialize bonsai in it
+    Clone {
+        /// Repository URL or local path
+        url: String,
+
+        /// Directory to clone into (defaults to the repository name)
+        #[arg(value_hint = ValueHint::DirPath)]
+        dir: Option<String>,
+
+        /// Use a bare repository in `<dir>/.bare` with worktrees beside it
+        #[arg(long)]
+        bare: bool,
+
+        /// Config template to initialize with (defaults to a committed `.bonsai.toml`)
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        config: Option<String>,
+
+        /// Skip post_create hooks for the worktrees created
+        #[arg(long)]
+        no_hooks: bool,
+
+        /// Where to ignore the worktree directory; the default keeps the clone clean
+        #[arg(long, value_enum, default_value_t = ExcludeMode::InfoExclude)]
+        exclude_mode: ExcludeMode,
     },
 
     /// Add a new worktree
     Add {
-        /// Branch name
-        branch: String,
+        /// Branch name (generated from `branches.template` with --from-title or --issue)
+        #[arg(
+            required_unless_present_any = ["from_title", "issue"],
+            add = ArgValueCandidates::new(complete::branches)
+        )]
+        branch: Option<String>,
 
         /// Create a new branch
         #[arg(short = 'c', long = "create")]
         create: bool,
 
         /// Base branch for new branch creation (used with -c)
-        #[arg(long)]
+        #[arg(long, add = ArgValueCandidates::new(complete::branches))]
         base: Option<String>,
 
         /// Custom worktree path
-        #[arg(long)]
+        #[arg(long, value_hint = ValueHint::DirPath)]
         path: Option<String>,
 
         /// Worktree name
@@ -57,11 +121,32 @@ pub enum Commands {
         /// Skip post_create hooks
         #[arg(long)]
         no_hooks: bool,
+
+        /// Change into the new worktree (needs shell integration)
+        #[arg(long)]
+        cd: bool,
+
+        /// Config profile to use instead of the one matching the branch
+        #[arg(long, add = ArgValueCandidates::new(complete::profiles))]
+        profile: Option<String>,
+
+        /// Create a branch named after this issue title
+        #[arg(long, value_name = "TITLE", conflicts_with_all = ["branch", "detach"])]
+        from_title: Option<String>,
+
+        /// Issue key for the generated name, recorded for the branch
+        #[arg(long, value_name = "KEY")]
+        issue: Option<String>,
+
+        /// `{type}` in the generated name (defaults to `branches.default_type`)
+        #[arg(long = "type", value_name = "TYPE", conflicts_with = "branch")]
+        kind: Option<String>,
     },
 
     /// Remove a worktree
     Remove {
         /// Worktree name or branch
+        #[arg(add = ArgValueCandidates::new(complete::worktrees))]
         worktree: String,
 
         /// Also delete the branch
@@ -71,6 +156,33 @@ pub enum Commands {
         /// Force removal even with uncommitted changes
         #[arg(long)]
         force: bool,
+
+        /// Remove even if unpushed commits, untracked or protected files would be lost
+        #[arg(long)]
+        discard_unpushed: bool,
+
+        /// Snapshot the branch tip and uncommitted changes so the worktree can be restored
+        #[arg(long)]
+        trash: bool,
+    },
+
+    /// Restore a worktree removed with --trash
+    Restore {
+        /// Trash entry id, branch or directory name
+        #[arg(add = ArgValueCandidates::new(complete::trash_entries))]
+        name: String,
+    },
+
+    /// Manage snapshots of removed worktrees
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommands,
+    },
+
+    /// Read, change and check `.bonsai.toml`
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
     },
 
     /// List worktrees
@@ -91,19 +203,28 @@ pub enum Commands {
     /// Print worktree path (use @ for main worktree)
     Cd {
         /// Worktree name or @ for main
+        #[arg(add = ArgValueCandidates::new(complete::worktrees_or_main))]
+        worktree: String,
+    },
+
+    /// Change the shell's directory to a worktree (needs shell integration)
+    Switch {
+        /// Worktree name or @ for main
+        #[arg(add = ArgValueCandidates::new(complete::worktrees_or_main))]
         worktree: String,
     },
 
     /// Show worktree status
     Status {
         /// Specific worktree (all if omitted)
+        #[arg(add = ArgValueCandidates::new(complete::worktrees))]
         worktree: Option<String>,
     },
 
     /// Remove stale or merged worktrees
     Prune {
         /// Remove worktrees merged into base branch
-        #[arg(long)]
+        #[arg(long, add = ArgValueCandidates::new(complete::branches))]
         merged: Option<Option<String>>,
 
         /// Remove worktrees with no commits in N days
@@ -121,11 +242,69 @@ pub enum Commands {
         /// Skip confirmation prompt
         #[arg(short, long)]
         yes: bool,
+
+        /// Also remove worktrees that would lose unpushed commits or files
+        #[arg(long)]
+        discard_unpushed: bool,
+    },
+
+    /// Fetch once and update worktrees from their upstream or base branch
+    Sync {
+        /// Worktrees to sync
+        #[arg(
+            required_unless_present = "all",
+            add = ArgValueCandidates::new(complete::worktrees)
+        )]
+        worktrees: Vec<String>,
+
+        /// Sync every worktree
+        #[arg(long, conflicts_with = "worktrees")]
+        all: bool,
+
+        /// Rebase instead of fast-forwarding (overrides config)
+        #[arg(long)]
+        rebase: bool,
+
+        /// Skip the initial fetch
+        #[arg(long)]
+        no_fetch: bool,
+
+        /// Number of worktrees to update in parallel
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
+
+    /// Run a command in every worktree
+    Exec {
+        /// Only worktrees whose branch or directory matches (glob, repeatable)
+        #[arg(long, add = ArgValueCandidates::new(complete::worktrees))]
+        filter: Vec<String>,
+
+        /// Number of worktrees to run in parallel
+        #[arg(short, long, default_value_t = 1)]
+        parallel: usize,
+
+        /// How to present each worktree's output
+        #[arg(long, value_enum, default_value_t = ExecOutput::Prefix)]
+        output: ExecOutput,
+
+        /// Stop starting new worktrees after the first failure
+        #[arg(long)]
+        fail_fast: bool,
+
+        /// Run in every worktree regardless of failures (default)
+        #[arg(long, conflicts_with = "fail_fast")]
+        keep_going: bool,
+
+        /// Command and arguments to run
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
     },
 
     /// Rename a worktree branch and move its directory
     Rename {
         /// Current branch name
+        #[arg(add = ArgValueCandidates::new(complete::worktrees))]
         old: String,
         /// New branch name
         new: String,
@@ -134,14 +313,43 @@ pub enum Commands {
     /// Move a worktree to a new path
     Move {
         /// Worktree name or branch
+        #[arg(add = ArgValueCandidates::new(complete::worktrees))]
         worktree: String,
         /// New path
+        #[arg(value_hint = ValueHint::DirPath)]
         new_path: String,
     },
 
+    /// Move worktrees created outside bonsai into the managed directory
+    Adopt {
+        /// Worktree path, branch or directory name
+        #[arg(required_unless_present = "all", value_hint = ValueHint::DirPath)]
+        path: Option<String>,
+
+        /// Adopt every worktree outside the managed directory
+        #[arg(long, conflicts_with = "path")]
+        all: bool,
+
+        /// Run post_create hooks in each adopted worktree
+        #[arg(long)]
+        run_hooks: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Revert the last rename, move, adopt, remove or prune
+    Undo {
+        /// Undo without asking, even if the repository has changed since
+        #[arg(short, long)]
+        yes: bool,
+    },
+
     /// Lock a worktree
     Lock {
         /// Worktree name or branch
+        #[arg(add = ArgValueCandidates::new(complete::worktrees))]
         worktree: String,
         /// Reason for locking
         #[arg(long)]
@@ -151,18 +359,123 @@ pub enum Commands {
     /// Unlock a worktree
     Unlock {
         /// Worktree name or branch
+        #[arg(add = ArgValueCandidates::new(complete::worktrees))]
         worktree: String,
     },
 
+    /// Diagnose and repair worktree inconsistencies
+    Doctor {
+        /// Apply the suggested fixes
+        #[arg(long)]
+        fix: bool,
+    },
+
     /// Generate shell completions
     Completion {
         /// Shell type
         shell: Shell,
     },
 
+    /// Print a short segment describing the current worktree for shell prompts
+    Prompt {
+        /// Segment template (placeholders: {name}, {dir}, {status}, {lock})
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Leave out the status, which is the only part that runs git
+        #[arg(long)]
+        no_status: bool,
+    },
+
     /// Print shell integration script
     ShellInit {
         /// Shell type
-        shell: Shell,
+        shell: InitShell,
+
+        /// Also show the current worktree in the shell prompt
+        #[arg(long)]
+        prompt: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TrashCommands {
+    /// List snapshots of removed worktrees
+    List,
+
+    /// Delete snapshots permanently
+    Purge {
+        /// Only purge snapshots older than this age (e.g. 30m, 12h, 7d, 2w)
+        #[arg(long)]
+        older_than: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Print a value by dotted key, e.g. `defaults.worktree_dir`
+    Get {
+        /// Dotted key
+        key: String,
     },
+
+    /// Set a value by dotted key, keeping comments in the file
+    Set {
+        /// Dotted key
+        key: String,
+        /// TOML value; anything else is stored as a string
+        value: String,
+    },
+
+    /// Open the config in $VISUAL or $EDITOR, then validate it
+    Edit,
+
+    /// Check the config for errors and unknown keys
+    Validate,
+
+    /// Print the effective configuration, defaults included
+    Show {
+        /// Apply the profile that matches this branch
+        #[arg(long = "for", value_name = "BRANCH", add = ArgValueCandidates::new(complete::branches))]
+        branch: Option<String>,
+    },
+
+    /// Rewrite the config in the current schema version
+    Migrate,
+
+    /// Print the JSON Schema for `.bonsai.toml`
+    Schema,
+}
+
+/// Shells supported by `shell-init`. Nushell has no `clap_complete::Shell`
+/// counterpart, so this is separate from the `completion` shell list.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum InitShell {
+    Bash,
+    Zsh,
+    Fish,
+    #[value(name = "powershell", alias = "pwsh")]
+    PowerShell,
+    Elvish,
+    #[value(alias = "nu")]
+    Nushell,
+}
+
+/// Where `init` and `clone` ignore the worktree directory.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExcludeMode {
+    /// The tracked `.gitignore`
+    Gitignore,
+    /// `.git/info/exclude`, which is local and never committed
+    InfoExclude,
+    /// Do not ignore it
+    None,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExecOutput {
+    /// Prefix each line with the worktree name as it is produced
+    Prefix,
+    /// Print each worktree's output as one block when it finishes
+    Group,
 }
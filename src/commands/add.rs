@@ -2,25 +2,54 @@ use std::path::PathBuf;
 
 use colored::Colorize;
 
+use crate::commands::switch::print_no_shell_hint;
 use crate::config::Config;
+use crate::directive;
 use crate::error::{BonsaiError, Result};
 use crate::git::runner::GitRunner;
 use crate::git::worktree::AddOptions;
 use crate::hooks;
+use crate::naming;
 
 pub struct AddArgs {
-    pub branch: String,
+    /// Branch to check out or create; generated from the issue and title
+    /// when `None`.
+    pub branch: Option<String>,
     pub create: bool,
     pub base: Option<String>,
     pub path: Option<String>,
     pub name: Option<String>,
     pub detach: bool,
     pub no_hooks: bool,
+    pub cd: bool,
+    pub profile: Option<String>,
+    pub from_title: Option<String>,
+    pub issue: Option<String>,
+    pub kind: Option<String>,
 }
 
 pub fn exec(git: &GitRunner, args: AddArgs, no_color: bool) -> Result<()> {
-    let config_path = git.repo_root.join(".bonsai.toml");
-    let config = Config::load(&config_path)?;
+    let config = Config::load(&git.config_path)?;
+
+    // A generated name is always for a new branch
+    let generated = args.branch.is_none();
+    let (branch, create) = match args.branch {
+        Some(branch) => (branch, args.create),
+        None => {
+            let branch = naming::generate(
+                &config.branches,
+                args.kind.as_deref(),
+                args.issue.as_deref(),
+                args.from_title.as_deref(),
+            )?;
+            (branch, true)
+        }
+    };
+    let config = config.for_branch(&branch, args.profile.as_deref())?;
+
+    if create {
+        naming::check(git, &config.branches, &branch)?;
+    }
 
     let worktree_dir = &config.defaults.worktree_dir;
 
@@ -28,44 +57,73 @@ pub fn exec(git: &GitRunner, args: AddArgs, no_color: bool) -> Result<()> {
     let wt_path: PathBuf = if let Some(ref custom_path) = args.path {
         PathBuf::from(custom_path)
     } else {
-        let dir_name = args
-            .name
-            .as_deref()
-            .unwrap_or(&args.branch)
-            .replace('/', "-");
-        git.repo_root.join(worktree_dir).join(&dir_name)
+        let dir_name = args.name.as_deref().unwrap_or(&branch).replace('/', "-");
+        // Collecting components drops the `.` of a bare layout's `worktree_dir`
+        git.repo_root
+            .join(worktree_dir)
+            .join(&dir_name)
+            .components()
+            .collect()
     };
 
     if wt_path.exists() {
         return Err(BonsaiError::WorktreeExists {
-            name: args.branch.clone(),
+            name: branch.clone(),
         });
     }
 
     // Check branch existence
-    if args.create {
-        if git.branch_exists(&args.branch)? {
+    if create {
+        if git.branch_exists(&branch)? {
             return Err(BonsaiError::BranchExists {
-                name: args.branch.clone(),
+                name: branch.clone(),
             });
         }
-    } else if !args.detach && !git.branch_exists(&args.branch)? {
+    } else if !args.detach && !git.branch_exists(&branch)? {
         return Err(BonsaiError::BranchNotFound {
-            name: args.branch.clone(),
+            name: branch.clone(),
         });
     }
 
+    // New branches start from the default branch unless a base is given
+    let base = if create {
+        args.base
+            .or_else(|| git.default_branch(config.defaults.base_branch.as_deref()))
+    } else {
+        args.base
+    };
+
     let opts = AddOptions {
-        create_branch: args.create,
-        base: args.base,
+        create_branch: create,
+        base,
         detach: args.detach,
     };
 
-    git.worktree_add(&wt_path, &args.branch, &opts)?;
+    git.worktree_add(&wt_path, &branch, &opts)?;
+
+    if create {
+        if let Some(ref base) = opts.base {
+            git.set_branch_base(&branch, base)?;
+        }
+    }
+    if !args.detach {
+        if let Some(ref profile) = args.profile {
+            git.set_branch_profile(&branch, profile)?;
+        }
+        if let Some(issue) = args.issue.as_deref().map(str::trim) {
+            git.set_branch_issue(&branch, issue)?;
+        }
+    }
 
     if no_color {
+        if generated {
+            eprintln!("Created branch {branch}");
+        }
         eprintln!("Created worktree at {}", wt_path.display());
     } else {
+        if generated {
+            eprintln!("{} branch {}", "Created".green(), branch.cyan());
+        }
         eprintln!(
             "{} worktree at {}",
             "Created".green(),
@@ -83,5 +141,12 @@ pub fn exec(git: &GitRunner, args: AddArgs, no_color: bool) -> Result<()> {
         )?;
     }
 
+    if args.cd && !git.dry_run {
+        let path = std::fs::canonicalize(&wt_path).unwrap_or(wt_path);
+        if !directive::cd(&path)? {
+            print_no_shell_hint(no_color);
+        }
+    }
+
     Ok(())
 }
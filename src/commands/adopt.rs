@@ -0,0 +1,202 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use crate::config::Config;
+use crate::directive;
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{registered_path, WorktreeInfo};
+use crate::hooks;
+use crate::journal::{Journal, Step};
+
+pub struct AdoptArgs {
+    /// Worktree to adopt; every foreign worktree when `None` (`--all`).
+    pub path: Option<String>,
+    pub run_hooks: bool,
+    pub yes: bool,
+}
+
+pub fn exec(git: &GitRunner, args: AdoptArgs, no_color: bool) -> Result<()> {
+    let config = Config::load(&git.config_path)?;
+    let managed_dir = git.repo_root.join(&config.defaults.worktree_dir);
+
+    let worktrees = git.worktree_list()?;
+    // Each candidate with where it actually is, which differs from the path
+    // git has registered for a directory moved by hand
+    let candidates: Vec<(&WorktreeInfo, PathBuf)> = match args.path {
+        Some(ref name) => vec![find_worktree(&worktrees, name)?],
+        None => worktrees
+            .iter()
+            .filter(|w| !w.is_main && !w.path.starts_with(&managed_dir))
+            .map(|w| (w, w.path.clone()))
+            .collect(),
+    };
+
+    let mut plan: Vec<Adoption> = Vec::new();
+    for (wt, from) in candidates {
+        if from.starts_with(&managed_dir) {
+            eprintln!("{} is already managed by bonsai", from.display());
+            continue;
+        }
+        if wt.is_locked {
+            eprintln!("Skipping locked worktree {}", from.display());
+            continue;
+        }
+        let dir_name = match wt.branch {
+            Some(ref branch) => branch.replace('/', "-"),
+            None => wt
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "worktree".to_string()),
+        };
+        let target = managed_dir.join(dir_name);
+        if target.exists() || plan.iter().any(|a| a.target == target) {
+            eprintln!(
+                "Skipping {}: {} already exists",
+                from.display(),
+                target.display()
+            );
+            continue;
+        }
+        plan.push(Adoption { wt, from, target });
+    }
+
+    if plan.is_empty() {
+        if no_color {
+            eprintln!("Nothing to adopt.");
+        } else {
+            eprintln!("{}", "Nothing to adopt.".bright_black());
+        }
+        return Ok(());
+    }
+
+    eprintln!("Worktrees to adopt:");
+    for Adoption { wt, from, target } in &plan {
+        let branch = wt.branch.as_deref().unwrap_or("(detached)");
+        if no_color {
+            eprintln!("  - {branch}: {} -> {}", from.display(), target.display());
+        } else {
+            eprintln!(
+                "  - {}: {} -> {}",
+                branch.cyan(),
+                from.display().to_string().yellow(),
+                target.display().to_string().yellow()
+            );
+        }
+    }
+
+    if !args.yes {
+        eprint!("Proceed? [y/N] ");
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            eprintln!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    if !git.dry_run {
+        std::fs::create_dir_all(&managed_dir)?;
+    }
+
+    let mut journal = Journal::begin(git, "adopt")?;
+    for Adoption { wt, from, target } in &plan {
+        let rel = directive::cwd_within(from);
+        // Undoing puts it back where it was found, registered there
+        journal.step(
+            Step::WorktreeMove {
+                from: from.clone(),
+                to: target.clone(),
+            },
+            || {
+                if from != &wt.path {
+                    git.worktree_repair(&[from])?;
+                }
+                git.worktree_move(from, target)
+            },
+            no_color,
+        )?;
+        if !git.dry_run {
+            directive::follow(rel, target)?;
+        }
+
+        // Record a base so status and prune treat it like a worktree made by `add`
+        let settings = config.for_worktree(git, wt.branch.as_deref());
+        if let Some(ref branch) = wt.branch {
+            if git.branch_base(branch).is_none() {
+                if let Some(base) = git.default_branch(settings.defaults.base_branch.as_deref()) {
+                    if &base != branch {
+                        git.set_branch_base(branch, &base)?;
+                    }
+                }
+            }
+        }
+
+        if no_color {
+            eprintln!("Adopted {}", target.display());
+        } else {
+            eprintln!(
+                "{} {}",
+                "Adopted".green(),
+                target.display().to_string().yellow()
+            );
+        }
+
+        if args.run_hooks && !settings.hooks.post_create.is_empty() {
+            hooks::run_hooks(
+                &settings.hooks.post_create,
+                &git.repo_root,
+                target,
+                no_color,
+            )?;
+        }
+    }
+
+    journal.finish()
+}
+
+struct Adoption<'a> {
+    wt: &'a WorktreeInfo,
+    /// Current location, which git may not know about yet.
+    from: PathBuf,
+    target: PathBuf,
+}
+
+fn resolve(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Match a worktree by path, branch or directory name, along with where it
+/// is now. A path git has lost track of (e.g. moved by hand) is matched
+/// through the registration its `.git` file points back to.
+fn find_worktree<'a>(
+    worktrees: &'a [WorktreeInfo],
+    name: &str,
+) -> Result<(&'a WorktreeInfo, PathBuf)> {
+    let path = resolve(name);
+    let registered = worktrees.iter().any(|w| w.path == path);
+    let lost = (!registered && path.join(".git").is_file())
+        .then(|| registered_path(&path))
+        .flatten();
+    worktrees
+        .iter()
+        .find(|w| {
+            w.path == path
+                || lost.as_ref() == Some(&w.path)
+                || w.branch.as_deref() == Some(name)
+                || w.path.file_name().map(|n| n.to_string_lossy()) == Some(name.into())
+        })
+        .filter(|w| !w.is_main)
+        .map(|w| {
+            let from = if lost.is_some() { path } else { w.path.clone() };
+            (w, from)
+        })
+        .ok_or_else(|| BonsaiError::WorktreeNotFound {
+            name: name.to_string(),
+        })
+}
@@ -1,17 +1,27 @@
+use std::path::PathBuf;
+
 use crate::error::{BonsaiError, Result};
 use crate::git::runner::GitRunner;
 
 pub fn exec(git: &GitRunner, worktree: &str) -> Result<()> {
+    let path = resolve(git, worktree)?;
+    println!("{}", path.display());
+    Ok(())
+}
+
+/// Path of the worktree named by branch or directory, or `@` for main.
+pub(crate) fn resolve(git: &GitRunner, worktree: &str) -> Result<PathBuf> {
     let worktrees = git.worktree_list()?;
 
     if worktree == "@" {
-        let main_wt = worktrees
-            .first()
-            .ok_or_else(|| BonsaiError::WorktreeNotFound {
-                name: "@".to_string(),
-            })?;
-        println!("{}", main_wt.path.display());
-        return Ok(());
+        let main_wt =
+            worktrees
+                .iter()
+                .find(|w| w.is_main)
+                .ok_or_else(|| BonsaiError::WorktreeNotFound {
+                    name: "@ (a bare repository has no main worktree)".to_string(),
+                })?;
+        return Ok(main_wt.path.clone());
     }
 
     let wt = worktrees
@@ -24,6 +34,5 @@ pub fn exec(git: &GitRunner, worktree: &str) -> Result<()> {
             name: worktree.to_string(),
         })?;
 
-    println!("{}", wt.path.display());
-    Ok(())
+    Ok(wt.path.clone())
 }
@@ -0,0 +1,205 @@
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use crate::cli::ExcludeMode;
+use crate::commands::{add, init};
+use crate::config::Config;
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::{BareLayout, GitRunner};
+
+pub struct CloneArgs {
+    pub url: String,
+    pub dir: Option<String>,
+    pub bare: bool,
+    /// Team config template to initialize with instead of the defaults.
+    pub config: Option<String>,
+    pub no_hooks: bool,
+    pub exclude_mode: ExcludeMode,
+}
+
+pub fn exec(
+    cwd: &Path,
+    args: CloneArgs,
+    dry_run: bool,
+    verbose: bool,
+    no_color: bool,
+) -> Result<()> {
+    let dir = match args.dir {
+        Some(ref dir) => cwd.join(dir),
+        None => cwd.join(dir_from_url(&args.url)?),
+    };
+    // Checked up front, as the bare layout writes into `dir` around git
+    if dir.exists() && std::fs::read_dir(&dir)?.next().is_some() {
+        return Err(BonsaiError::DestinationExists { path: dir });
+    }
+
+    // Read before cloning so a bad template does not leave a half set up clone
+    let template = match args.config {
+        Some(ref path) => Some(init::read_template(&cwd.join(path))?),
+        None => None,
+    };
+
+    let outside = GitRunner::new(cwd.to_path_buf(), dry_run, verbose, no_color);
+    if args.bare {
+        clone_bare(&outside, &args.url, &dir)?;
+    } else {
+        outside.run(&["clone", &args.url, &dir.to_string_lossy()])?;
+    }
+
+    // Nothing was cloned, so there is nothing to initialize
+    if dry_run {
+        return Ok(());
+    }
+
+    let git = GitRunner::discover(&dir, dry_run, verbose, no_color)?;
+    let config = write_config(&git, template)?;
+    init::prepare(&git, &config, args.exclude_mode, no_color)?;
+
+    // The main worktree already has the default branch checked out; a bare
+    // repository needs a worktree for it like any other branch
+    let head = git.run(&["symbolic-ref", "--short", "HEAD"])?;
+    let mut branches = Vec::new();
+    if args.bare {
+        branches.push(head.clone());
+    }
+    for branch in &config.clone.branches {
+        if (args.bare || branch != &head) && !branches.contains(branch) {
+            branches.push(branch.clone());
+        }
+    }
+
+    for branch in branches {
+        if !track(&git, &branch)? {
+            if no_color {
+                eprintln!("Skipping {branch}: no such branch on origin");
+            } else {
+                eprintln!(
+                    "{} {}: no such branch on origin",
+                    "Skipping".yellow(),
+                    branch.cyan()
+                );
+            }
+            continue;
+        }
+        add::exec(
+            &git,
+            add::AddArgs {
+                branch: Some(branch),
+                create: false,
+                base: None,
+                path: None,
+                name: None,
+                detach: false,
+                no_hooks: args.no_hooks,
+                cd: false,
+                profile: None,
+                from_title: None,
+                issue: None,
+                kind: None,
+            },
+            no_color,
+        )?;
+    }
+
+    if no_color {
+        eprintln!("Cloned into {}", dir.display());
+    } else {
+        eprintln!(
+            "{} into {}",
+            "Cloned".green(),
+            dir.display().to_string().yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Put the config in place: the given template, else one committed to the
+/// repository, else the defaults. A bare layout keeps its own `worktree_dir`.
+fn write_config(git: &GitRunner, template: Option<String>) -> Result<Config> {
+    // A regular clone has the committed config checked out already
+    let committed = || {
+        if git.bare {
+            git.run(&["show", "HEAD:.bonsai.toml"]).ok()
+        } else {
+            None
+        }
+    };
+    match template.or_else(committed) {
+        Some(content) => init::install_config(git, content),
+        None if git.config_path.exists() => Config::load(&git.config_path),
+        None => {
+            let mut config = Config::default_config();
+            if git.bare {
+                config.defaults.worktree_dir = BareLayout::of(&git.work_dir).worktree_dir;
+            }
+            config.save(&git.config_path)?;
+            Ok(config)
+        }
+    }
+}
+
+/// Make `branch` a local branch tracking `origin/<branch>`. Returns `false`
+/// when it exists neither locally nor on origin.
+fn track(git: &GitRunner, branch: &str) -> Result<bool> {
+    let remote = format!("origin/{branch}");
+    let on_remote = git
+        .run(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/remotes/{remote}"),
+        ])
+        .is_ok();
+    if git.branch_exists(branch)? {
+        if on_remote {
+            git.run(&["branch", &format!("--set-upstream-to={remote}"), branch])?;
+        }
+        return Ok(true);
+    }
+    if !on_remote {
+        return Ok(false);
+    }
+    git.run(&["branch", "--track", branch, &remote])?;
+    Ok(true)
+}
+
+/// Clone into `<dir>/.bare` with a `.git` file pointing at it, so git
+/// commands work from `<dir>` and worktrees sit next to the repository.
+fn clone_bare(outside: &GitRunner, url: &str, dir: &Path) -> Result<()> {
+    let repo = dir.join(".bare");
+    outside.run(&["clone", "--bare", url, &repo.to_string_lossy()])?;
+    if outside.dry_run {
+        return Ok(());
+    }
+    std::fs::write(dir.join(".git"), "gitdir: ./.bare\n")?;
+
+    // A bare clone maps branches straight onto local ones; fetch into
+    // remote-tracking branches like a regular clone so sync and prune work
+    let git = GitRunner::new(repo, false, outside.verbose, outside.no_color);
+    git.run(&[
+        "config",
+        "remote.origin.fetch",
+        "+refs/heads/*:refs/remotes/origin/*",
+    ])?;
+    git.run(&["fetch", "origin"])?;
+    git.run(&["remote", "set-head", "origin", "--auto"])?;
+    Ok(())
+}
+
+/// Directory name git itself would pick: the last path component without
+/// a trailing `.git`.
+fn dir_from_url(url: &str) -> Result<PathBuf> {
+    let last = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':', '\\'])
+        .next()
+        .unwrap_or_default();
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    if name.is_empty() {
+        return Err(BonsaiError::Config(format!(
+            "cannot derive a directory name from {url}; pass one explicitly"
+        )));
+    }
+    Ok(PathBuf::from(name))
+}
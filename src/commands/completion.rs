@@ -6,7 +6,7 @@ use crate::cli::Cli;
 use crate::error::Result;
 
 /// Binary names to generate completions for
-const BIN_NAMES: &[&str] = &["bonsai", "bn"];
+pub(crate) const BIN_NAMES: &[&str] = &["bonsai", "bn"];
 
 pub fn exec(shell: Shell) -> Result<()> {
     for bin_name in BIN_NAMES {
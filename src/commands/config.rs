@@ -0,0 +1,236 @@
+use std::process::Command;
+
+use colored::Colorize;
+use toml_edit::{Item, Table, Value};
+
+use crate::config::{self, Config, CONFIG_VERSION};
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+
+/// Print the effective value of a dotted key such as `defaults.worktree_dir`.
+pub fn exec_get(git: &GitRunner, key: &str) -> Result<()> {
+    let config = Config::load(&git.config_path)?;
+    let mut value = toml::Value::Table(effective(&config)?);
+    for part in key.split('.') {
+        value = value
+            .get(part)
+            .cloned()
+            .ok_or_else(|| BonsaiError::Config(format!("`{key}` is not set")))?;
+    }
+
+    match value {
+        toml::Value::String(s) => println!("{s}"),
+        toml::Value::Table(table) => {
+            print!("{}", toml::to_string_pretty(&table).unwrap_or_default())
+        }
+        other => println!("{other}"),
+    }
+    Ok(())
+}
+
+/// Set a dotted key, keeping comments and layout. `value` is read as TOML
+/// (`true`, `5`, `[".env"]`) and falls back to a plain string.
+pub fn exec_set(git: &GitRunner, key: &str, value: &str, no_color: bool) -> Result<()> {
+    let path = &git.config_path;
+    let content = config::read(path)?;
+    let mut doc = config::parse_document(&content, path)?;
+    config::migrate(&mut doc, path)?;
+
+    let parts: Vec<&str> = key.split('.').collect();
+    let Some((last, tables)) = parts.split_last().filter(|(l, _)| !l.is_empty()) else {
+        return Err(BonsaiError::Config(format!("invalid key `{key}`")));
+    };
+
+    let mut item = doc.as_item_mut();
+    for (i, part) in tables.iter().enumerate() {
+        if !item.is_none() && !item.is_table_like() {
+            return Err(BonsaiError::Config(format!(
+                "`{}` is not a table",
+                parts[..i].join(".")
+            )));
+        }
+        // A real `[section]` rather than the inline table indexing would create
+        if item.get(part).is_none() {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            item[part] = Item::Table(table);
+        }
+        item = &mut item[part];
+    }
+    if !item.is_none() && !item.is_table_like() {
+        return Err(BonsaiError::Config(format!(
+            "`{}` is not a table",
+            tables.join(".")
+        )));
+    }
+
+    let mut new_value = value
+        .parse::<Value>()
+        .unwrap_or_else(|_| Value::from(value));
+    if let Some(old) = item.get(last).and_then(Item::as_value) {
+        *new_value.decor_mut() = old.decor().clone();
+    }
+    item[last] = Item::Value(new_value);
+
+    // Refuse to write a file that would no longer load
+    let updated = doc.to_string();
+    Config::parse(&updated, path)?;
+    if git.dry_run {
+        eprintln!("[dry-run] set {key} = {value} in {}", path.display());
+        return Ok(());
+    }
+    std::fs::write(path, updated)?;
+
+    if no_color {
+        eprintln!("Set {key} = {value}");
+    } else {
+        eprintln!("{} {} = {}", "Set".green(), key.cyan(), value.yellow());
+    }
+    Ok(())
+}
+
+/// Open the config in `$VISUAL`/`$EDITOR` and validate the result.
+pub fn exec_edit(git: &GitRunner, no_color: bool) -> Result<()> {
+    let path = &git.config_path;
+    if !path.exists() {
+        return Err(BonsaiError::NotInitialized);
+    }
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    // Through the shell, so EDITOR may carry arguments such as `code --wait`
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("sh")
+        .arg(path)
+        .status()
+        .map_err(|e| BonsaiError::Config(format!("failed to run editor '{editor}': {e}")))?;
+    if !status.success() {
+        return Err(BonsaiError::Config(format!(
+            "editor '{editor}' exited with {status}"
+        )));
+    }
+
+    exec_validate(git, no_color)
+}
+
+/// Check the config and report the first problem with its location.
+pub fn exec_validate(git: &GitRunner, no_color: bool) -> Result<()> {
+    let path = &git.config_path;
+    let content = config::read(path)?;
+    Config::parse(&content, path)?;
+
+    let doc = config::parse_document(&content, path)?;
+    let version = config::schema_version(&doc, path)?;
+
+    if no_color {
+        eprintln!("{} is valid", path.display());
+    } else {
+        eprintln!(
+            "{} {}",
+            path.display().to_string().yellow(),
+            "is valid".green()
+        );
+    }
+    if version < CONFIG_VERSION {
+        eprintln!(
+            "Schema version {version} is older than {CONFIG_VERSION}; run `bonsai config migrate` to update the file"
+        );
+    }
+    Ok(())
+}
+
+/// Print the effective configuration, defaults included. With a branch,
+/// its profile is applied and named in a leading comment.
+pub fn exec_show(git: &GitRunner, branch: Option<&str>) -> Result<()> {
+    let mut config = Config::load(&git.config_path)?;
+    if let Some(branch) = branch {
+        let recorded = git.branch_profile(branch);
+        let name = config.defined_profile(recorded.as_deref());
+        match config.profile(branch, name)? {
+            Some(profile) => println!("# {branch}: profile `{}`", profile.name),
+            None => println!("# {branch}: no profile"),
+        }
+        config = config.for_branch(branch, name)?;
+    }
+    let table = effective(&config)?;
+    print!(
+        "{}",
+        toml::to_string_pretty(&table)
+            .map_err(|e| BonsaiError::Config(format!("failed to serialize config: {e}")))?
+    );
+    Ok(())
+}
+
+/// Rewrite an older config file in the current schema.
+pub fn exec_migrate(git: &GitRunner, no_color: bool) -> Result<()> {
+    let path = &git.config_path;
+    let content = config::read(path)?;
+    let mut doc = config::parse_document(&content, path)?;
+    let from = config::schema_version(&doc, path)?;
+
+    if !config::migrate(&mut doc, path)? {
+        eprintln!("Already at schema version {CONFIG_VERSION}.");
+        return Ok(());
+    }
+    let updated = doc.to_string();
+    Config::parse(&updated, path)?;
+    if git.dry_run {
+        eprintln!(
+            "[dry-run] migrate {} to schema version {CONFIG_VERSION}",
+            path.display()
+        );
+        return Ok(());
+    }
+    std::fs::write(path, updated)?;
+
+    if no_color {
+        eprintln!("Migrated schema version {from} to {CONFIG_VERSION}");
+    } else {
+        eprintln!(
+            "{} schema version {from} to {CONFIG_VERSION}",
+            "Migrated".green()
+        );
+    }
+    Ok(())
+}
+
+/// Print the JSON Schema for `.bonsai.toml`. Draft 7, as editor plugins
+/// such as taplo expect.
+pub fn exec_schema() -> Result<()> {
+    let schema = schemars::generate::SchemaSettings::draft07()
+        .into_generator()
+        .into_root_schema_for::<Config>();
+    let json = serde_json::to_string_pretty(&schema)
+        .map_err(|e| BonsaiError::Config(format!("failed to serialize schema: {e}")))?;
+    println!("{json}");
+    Ok(())
+}
+
+/// The config as a table, with sections left out of the file at their defaults.
+fn effective(config: &Config) -> Result<toml::Table> {
+    let serialize =
+        |e: toml::ser::Error| BonsaiError::Config(format!("failed to serialize config: {e}"));
+    let mut table = toml::Table::try_from(config).map_err(serialize)?;
+    if !table.contains_key("prompt") {
+        table.insert(
+            "prompt".to_string(),
+            toml::Value::try_from(&config.prompt).map_err(serialize)?,
+        );
+    }
+    if !table.contains_key("clone") {
+        table.insert(
+            "clone".to_string(),
+            toml::Value::try_from(&config.clone).map_err(serialize)?,
+        );
+    }
+    if !table.contains_key("branches") {
+        table.insert(
+            "branches".to_string(),
+            toml::Value::try_from(&config.branches).map_err(serialize)?,
+        );
+    }
+    Ok(table)
+}
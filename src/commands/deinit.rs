@@ -0,0 +1,132 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::commands::init::{info_exclude, remove_line, EXCLUDE_KEY};
+use crate::commands::remove::{self, RemoveArgs};
+use crate::config::Config;
+use crate::error::Result;
+use crate::git::runner::GitRunner;
+
+pub struct DeinitArgs {
+    pub remove_worktrees: bool,
+    pub force: bool,
+    pub yes: bool,
+}
+
+pub fn exec(git: &GitRunner, args: DeinitArgs, no_color: bool) -> Result<()> {
+    let config = Config::load(&git.config_path)?;
+    let worktree_dir = config.defaults.worktree_dir.trim_end_matches('/');
+    let managed_dir = git.repo_root.join(worktree_dir);
+    let worktrees: Vec<_> = git
+        .worktree_list()?
+        .into_iter()
+        .filter(|w| !w.is_main && w.path.starts_with(&managed_dir))
+        .collect();
+
+    // Only undo an ignore entry init added itself, and keep it while
+    // worktrees stay behind in the directory it covers
+    let entry = format!("{worktree_dir}/");
+    let keep_worktrees = !args.remove_worktrees && !worktrees.is_empty();
+    let recorded = git.run(&["config", "--get", EXCLUDE_KEY]).ok();
+    let ignore_file: Option<PathBuf> = match recorded.as_deref() {
+        _ if keep_worktrees => None,
+        Some("gitignore") => Some(git.repo_root.join(".gitignore")),
+        Some("info-exclude") => Some(info_exclude(git)?),
+        _ => None,
+    };
+
+    eprintln!("This will remove:");
+    eprintln!("  - {}", git.config_path.display());
+    if let Some(ref file) = ignore_file {
+        eprintln!("  - the {entry} entry in {}", file.display());
+    }
+    if args.remove_worktrees {
+        for wt in &worktrees {
+            eprintln!("  - worktree {}", wt.path.display());
+        }
+    } else if keep_worktrees {
+        let message = format!(
+            "Keeping {} worktree(s) in {}; pass --remove-worktrees to remove them.",
+            worktrees.len(),
+            managed_dir.display()
+        );
+        if no_color {
+            eprintln!("{message}");
+        } else {
+            eprintln!("{}", message.yellow());
+        }
+    }
+
+    if !args.yes {
+        eprint!("Proceed? [y/N] ");
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            eprintln!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    // Worktrees first: `remove` still reads the config for protected files
+    if args.remove_worktrees {
+        for wt in &worktrees {
+            let name = match wt.branch {
+                Some(ref branch) => branch.clone(),
+                None => wt
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+            };
+            remove::exec(
+                git,
+                RemoveArgs {
+                    worktree: name,
+                    with_branch: false,
+                    force: args.force,
+                    discard_unpushed: args.force,
+                    trash: false,
+                },
+                no_color,
+            )?;
+        }
+    }
+
+    if git.dry_run {
+        eprintln!("[dry-run] remove {}", git.config_path.display());
+        if let Some(ref file) = ignore_file {
+            eprintln!("[dry-run] remove {entry} from {}", file.display());
+        }
+        return Ok(());
+    }
+
+    if let Some(ref file) = ignore_file {
+        let now_empty = remove_line(file, &entry)?;
+        // A `.gitignore` that only held the entry was created by init
+        if now_empty && file.ends_with(".gitignore") {
+            std::fs::remove_file(file)?;
+        }
+    }
+    if recorded.is_some() {
+        git.run(&["config", "--unset", EXCLUDE_KEY])?;
+    }
+    std::fs::remove_file(&git.config_path)?;
+    if managed_dir != git.repo_root {
+        // Only succeeds once the directory is empty
+        let _ = std::fs::remove_dir(&managed_dir);
+    }
+
+    if no_color {
+        eprintln!("Removed bonsai from {}", git.repo_root.display());
+    } else {
+        eprintln!(
+            "{} bonsai from {}",
+            "Removed".green(),
+            git.repo_root.display().to_string().yellow()
+        );
+    }
+    Ok(())
+}
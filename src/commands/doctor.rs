@@ -0,0 +1,297 @@
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use crate::commands::init::{update_gitignore, EXCLUDE_KEY};
+use crate::config::Config;
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{registered_path, WorktreeInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    fn label(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
+/// Repairs, listed in the order they are applied: repairing moved worktrees
+/// must happen before pruning, or git would forget them.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Fix {
+    Repair(PathBuf),
+    UnlockAndPrune(PathBuf),
+    Prune,
+    IgnoreWorktreeDir(String),
+}
+
+impl Fix {
+    fn describe(&self) -> String {
+        match self {
+            Fix::Repair(path) => format!("git worktree repair {}", path.display()),
+            Fix::UnlockAndPrune(path) => {
+                format!(
+                    "git worktree unlock {} && git worktree prune",
+                    path.display()
+                )
+            }
+            Fix::Prune => "git worktree prune".to_string(),
+            Fix::IgnoreWorktreeDir(entry) => format!("add {entry} to .gitignore"),
+        }
+    }
+
+    fn apply(&self, git: &GitRunner) -> Result<()> {
+        match self {
+            Fix::Repair(path) => git.worktree_repair(&[path]),
+            Fix::UnlockAndPrune(path) => {
+                git.worktree_unlock(path)?;
+                git.worktree_prune()
+            }
+            Fix::Prune => git.worktree_prune(),
+            Fix::IgnoreWorktreeDir(entry) => {
+                if git.dry_run {
+                    eprintln!("[dry-run] add {entry} to .gitignore");
+                    return Ok(());
+                }
+                update_gitignore(&git.repo_root, entry)
+            }
+        }
+    }
+}
+
+struct Finding {
+    severity: Severity,
+    message: String,
+    fix: Option<Fix>,
+}
+
+pub fn exec(git: &GitRunner, fix: bool, no_color: bool) -> Result<()> {
+    let findings = diagnose(git)?;
+
+    if findings.is_empty() {
+        if no_color {
+            eprintln!("No problems found.");
+        } else {
+            eprintln!("{}", "No problems found.".green());
+        }
+        return Ok(());
+    }
+
+    for finding in &findings {
+        print_finding(finding, no_color);
+    }
+
+    let problems = findings
+        .iter()
+        .filter(|f| f.severity != Severity::Info)
+        .count();
+
+    if !fix {
+        if problems > 0 {
+            return Err(BonsaiError::DoctorFoundProblems { count: problems });
+        }
+        return Ok(());
+    }
+
+    let mut fixes: Vec<Fix> = findings.iter().filter_map(|f| f.fix.clone()).collect();
+    fixes.sort();
+    fixes.dedup();
+
+    // One failed fix must not stop the others from being applied
+    eprintln!();
+    let mut failed = 0;
+    for f in &fixes {
+        match f.apply(git) {
+            Ok(()) if no_color => eprintln!("Fixed: {}", f.describe()),
+            Ok(()) => eprintln!("{} {}", "Fixed:".green(), f.describe()),
+            Err(e) => {
+                failed += 1;
+                if no_color {
+                    eprintln!("Failed: {}: {e}", f.describe());
+                } else {
+                    eprintln!("{} {}: {e}", "Failed:".red().bold(), f.describe());
+                }
+            }
+        }
+    }
+
+    let remaining = failed
+        + findings
+            .iter()
+            .filter(|f| f.severity != Severity::Info && f.fix.is_none())
+            .count();
+    if remaining > 0 {
+        return Err(BonsaiError::DoctorFoundProblems { count: remaining });
+    }
+
+    Ok(())
+}
+
+fn diagnose(git: &GitRunner) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let worktrees = git.worktree_list()?;
+    let config = Config::load_if_present(&git.config_path)?;
+
+    // Directories under the managed dir that git does not know about,
+    // along with the registered path their `.git` file points back to
+    let mut orphans: Vec<(PathBuf, Option<PathBuf>)> = Vec::new();
+    if let Some(ref config) = config {
+        let managed_dir = git.repo_root.join(&config.defaults.worktree_dir);
+        if managed_dir.is_dir() {
+            for entry in std::fs::read_dir(&managed_dir)? {
+                let path = entry?.path();
+                let registered = worktrees.iter().any(|w| w.path == path);
+                if !path.is_dir() || registered || path == git.work_dir {
+                    continue;
+                }
+                orphans.push((path.clone(), registered_path(&path)));
+            }
+        }
+    }
+
+    // git does not mark locked worktrees prunable, even on a dead path
+    let missing = |wt: &WorktreeInfo| wt.is_prunable || (wt.is_locked && !wt.path.exists());
+
+    for wt in &worktrees {
+        if wt.is_main || !missing(wt) {
+            continue;
+        }
+
+        let moved_to = orphans
+            .iter()
+            .find(|(_, registered)| registered.as_deref() == Some(wt.path.as_path()))
+            .map(|(path, _)| path.clone());
+
+        let finding = if let Some(new_path) = moved_to {
+            Finding {
+                severity: Severity::Error,
+                message: format!(
+                    "worktree {} was moved to {} without git",
+                    wt.path.display(),
+                    new_path.display()
+                ),
+                fix: Some(Fix::Repair(new_path)),
+            }
+        } else if wt.is_locked {
+            Finding {
+                severity: Severity::Warning,
+                message: format!("locked worktree {} no longer exists", wt.path.display()),
+                fix: Some(Fix::UnlockAndPrune(wt.path.clone())),
+            }
+        } else {
+            Finding {
+                severity: Severity::Warning,
+                message: format!(
+                    "stale .git/worktrees entry for missing {}",
+                    wt.path.display()
+                ),
+                fix: Some(Fix::Prune),
+            }
+        };
+        findings.push(finding);
+    }
+
+    for (path, registered) in &orphans {
+        let already_reported = registered
+            .as_ref()
+            .is_some_and(|r| worktrees.iter().any(|w| &w.path == r && missing(w)));
+        if already_reported {
+            continue;
+        }
+        if let Some(original) = registered.as_ref().filter(|r| r.exists()) {
+            // Repairing would take the registration away from the original
+            findings.push(Finding {
+                severity: Severity::Warning,
+                message: format!(
+                    "{} is a copy of worktree {}; delete it by hand",
+                    path.display(),
+                    original.display()
+                ),
+                fix: None,
+            });
+        } else if registered.is_some() {
+            // Its admin dir survives, so repair can point it back here
+            findings.push(Finding {
+                severity: Severity::Warning,
+                message: format!("worktree {} is not registered with git", path.display()),
+                fix: Some(Fix::Repair(path.clone())),
+            });
+        } else if path.join(".git").is_file() {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                message: format!(
+                    "worktree {} is not registered with git and its admin dir is gone; \
+                     move it aside and recreate it with `bonsai add`",
+                    path.display()
+                ),
+                fix: None,
+            });
+        } else {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                message: format!(
+                    "{} is not a worktree; move or delete it by hand",
+                    path.display()
+                ),
+                fix: None,
+            });
+        }
+    }
+
+    match config {
+        Some(ref config) => {
+            let dir = config.defaults.worktree_dir.trim_end_matches('/');
+            let inside_repo = !Path::new(dir).is_absolute() && !dir.starts_with("..");
+            // `init --exclude-mode none` leaves ignoring to the user on purpose
+            let opted_out = git
+                .run(&["config", "--get", EXCLUDE_KEY])
+                .is_ok_and(|mode| mode == "none");
+            if !git.bare && inside_repo && !opted_out && !git.is_ignored(&format!("{dir}/")) {
+                findings.push(Finding {
+                    severity: Severity::Warning,
+                    message: format!("{dir}/ is not ignored by git"),
+                    fix: Some(Fix::IgnoreWorktreeDir(format!("{dir}/"))),
+                });
+            }
+        }
+        None => findings.push(Finding {
+            severity: Severity::Info,
+            message: "bonsai is not initialized; skipped config checks".to_string(),
+            fix: None,
+        }),
+    }
+
+    findings.sort_by_key(|f| f.severity);
+    Ok(findings)
+}
+
+fn print_finding(finding: &Finding, no_color: bool) {
+    let label = format!("{:<7}", finding.severity.label());
+    if no_color {
+        eprintln!("{label} {}", finding.message);
+    } else {
+        let label = match finding.severity {
+            Severity::Error => label.red().bold(),
+            Severity::Warning => label.yellow(),
+            Severity::Info => label.bright_black(),
+        };
+        eprintln!("{label} {}", finding.message);
+    }
+    if let Some(ref fix) = finding.fix {
+        if no_color {
+            eprintln!("        fix: {}", fix.describe());
+        } else {
+            eprintln!("        {} {}", "fix:".bright_black(), fix.describe());
+        }
+    }
+}
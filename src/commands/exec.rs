@@ -0,0 +1,253 @@
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use colored::Colorize;
+
+use crate::cli::ExecOutput;
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+use crate::git::worktree::WorktreeInfo;
+use crate::pattern::glob_match;
+
+pub struct ExecArgs {
+    pub command: Vec<String>,
+    pub filter: Vec<String>,
+    pub parallel: usize,
+    pub output: ExecOutput,
+    pub fail_fast: bool,
+}
+
+enum Outcome {
+    Exited(i32),
+    Signaled,
+    SpawnFailed(String),
+    NotRun,
+}
+
+impl Outcome {
+    fn is_success(&self) -> bool {
+        matches!(self, Outcome::Exited(0))
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Outcome::Exited(code) => format!("exit {code}"),
+            Outcome::Signaled => "killed by signal".to_string(),
+            Outcome::SpawnFailed(e) => format!("failed to start: {e}"),
+            Outcome::NotRun => "not run (fail-fast)".to_string(),
+        }
+    }
+}
+
+pub fn exec(git: &GitRunner, args: ExecArgs, no_color: bool) -> Result<()> {
+    let worktrees = git.worktree_list()?;
+
+    let targets: Vec<&WorktreeInfo> = worktrees
+        .iter()
+        .filter(|w| {
+            args.filter.is_empty()
+                || args
+                    .filter
+                    .iter()
+                    .any(|f| glob_match(f, &display_name(w)) || glob_match(f, &dir_name(w)))
+        })
+        .collect();
+
+    if targets.is_empty() {
+        if no_color {
+            eprintln!("No worktrees matched.");
+        } else {
+            eprintln!("{}", "No worktrees matched.".bright_black());
+        }
+        return Ok(());
+    }
+
+    if git.dry_run {
+        for wt in &targets {
+            eprintln!(
+                "[dry-run] (cd {}) {}",
+                wt.path.display(),
+                args.command.join(" ")
+            );
+        }
+        return Ok(());
+    }
+
+    let jobs = args.parallel.clamp(1, targets.len());
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let print_lock = Mutex::new(());
+    let results: Mutex<Vec<Outcome>> =
+        Mutex::new((0..targets.len()).map(|_| Outcome::NotRun).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(wt) = targets.get(i) else {
+                    break;
+                };
+                let outcome =
+                    run_in_worktree(git, wt, &args.command, args.output, &print_lock, no_color);
+                if args.fail_fast && !outcome.is_success() {
+                    stop.store(true, Ordering::SeqCst);
+                }
+                results.lock().unwrap()[i] = outcome;
+            });
+        }
+    });
+
+    let results = results.into_inner().unwrap();
+    print_summary(&targets, &results, no_color);
+
+    // Worktrees skipped by --fail-fast did not fail; they are reported apart
+    let skipped = results
+        .iter()
+        .filter(|o| matches!(o, Outcome::NotRun))
+        .count();
+    let failed = results
+        .iter()
+        .filter(|o| !o.is_success() && !matches!(o, Outcome::NotRun))
+        .count();
+    if skipped > 0 {
+        eprintln!("Skipped {skipped} worktree(s) after the first failure.");
+    }
+    if failed > 0 {
+        return Err(BonsaiError::ExecFailed {
+            count: failed,
+            skipped,
+        });
+    }
+
+    Ok(())
+}
+
+fn run_in_worktree(
+    git: &GitRunner,
+    wt: &WorktreeInfo,
+    command: &[String],
+    output: ExecOutput,
+    print_lock: &Mutex<()>,
+    no_color: bool,
+) -> Outcome {
+    let name = display_name(wt);
+
+    let mut cmd = Command::new(&command[0]);
+    cmd.args(&command[1..])
+        .current_dir(&wt.path)
+        .env("BONSAI_WORKTREE", dir_name(wt))
+        .env("BONSAI_WORKTREE_PATH", &wt.path)
+        .env("BONSAI_BRANCH", wt.branch.as_deref().unwrap_or(""))
+        .env("BONSAI_REPO_ROOT", &git.repo_root)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(e) => return Outcome::SpawnFailed(e.to_string()),
+    };
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+
+    match output {
+        ExecOutput::Prefix => {
+            let prefix = if no_color {
+                format!("[{name}]")
+            } else {
+                format!("[{name}]").cyan().to_string()
+            };
+            std::thread::scope(|scope| {
+                scope.spawn(|| {
+                    for line in BufReader::new(stdout).lines().map_while(|l| l.ok()) {
+                        let _guard = print_lock.lock().unwrap();
+                        println!("{prefix} {line}");
+                    }
+                });
+                scope.spawn(|| {
+                    for line in BufReader::new(stderr).lines().map_while(|l| l.ok()) {
+                        let _guard = print_lock.lock().unwrap();
+                        eprintln!("{prefix} {line}");
+                    }
+                });
+            });
+        }
+        ExecOutput::Group => {
+            let (out, err) = std::thread::scope(|scope| {
+                let out = scope.spawn(|| read_all(stdout));
+                let err = scope.spawn(|| read_all(stderr));
+                (out.join().unwrap(), err.join().unwrap())
+            });
+            let _guard = print_lock.lock().unwrap();
+            if no_color {
+                println!("==> {name} ({})", wt.path.display());
+            } else {
+                println!(
+                    "{} {} ({})",
+                    "==>".blue().bold(),
+                    name.cyan().bold(),
+                    wt.path.display().to_string().yellow()
+                );
+            }
+            print!("{out}");
+            eprint!("{err}");
+        }
+    }
+
+    match child.wait() {
+        Ok(status) => match status.code() {
+            Some(code) => Outcome::Exited(code),
+            None => Outcome::Signaled,
+        },
+        Err(e) => Outcome::SpawnFailed(e.to_string()),
+    }
+}
+
+fn read_all(mut reader: impl Read) -> String {
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+fn print_summary(targets: &[&WorktreeInfo], results: &[Outcome], no_color: bool) {
+    let names: Vec<String> = targets.iter().map(|w| display_name(w)).collect();
+    let width = names.iter().map(|n| n.len()).max().unwrap_or(6).max(6);
+
+    eprintln!();
+    for (name, outcome) in names.iter().zip(results) {
+        let result = outcome.describe();
+        if no_color {
+            let mark = match outcome {
+                o if o.is_success() => "ok",
+                Outcome::NotRun => "skip",
+                _ => "FAIL",
+            };
+            eprintln!("{mark:<4} {name:<width$}  {result}");
+        } else {
+            let mark = match outcome {
+                o if o.is_success() => "ok  ".green(),
+                Outcome::NotRun => "skip".bright_black(),
+                _ => "FAIL".red(),
+            };
+            let padding = width.saturating_sub(name.len());
+            eprintln!("{mark} {}{:padding$}  {result}", name.cyan(), "");
+        }
+    }
+}
+
+fn display_name(wt: &WorktreeInfo) -> String {
+    wt.branch.clone().unwrap_or_else(|| dir_name(wt))
+}
+
+fn dir_name(wt: &WorktreeInfo) -> String {
+    wt.path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| wt.path.display().to_string())
+}
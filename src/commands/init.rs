@@ -1,27 +1,169 @@
-use std::path::Path;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 use colored::Colorize;
 
-use crate::config::Config;
+use clap::ValueEnum;
+
+use crate::cli::ExcludeMode;
+use crate::config::{Config, Hook};
+use crate::detect::{self, Suggestion};
 use crate::error::{BonsaiError, Result};
+use crate::git::runner::{BareLayout, GitRunner};
+
+pub struct InitArgs {
+    pub force: bool,
+    /// Template name (looked up in the user's template dir) or path.
+    pub template: Option<String>,
+    pub yes: bool,
+    pub exclude_mode: ExcludeMode,
+}
 
-pub fn exec(repo_root: &Path, force: bool, no_color: bool) -> Result<()> {
-    let config_path = repo_root.join(".bonsai.toml");
-    let worktree_dir = repo_root.join(".bonsai");
+/// Git config key recording which ignore file `init` added the worktree
+/// directory to, so `deinit` can take it out again. `none` if the user opted out.
+pub(crate) const EXCLUDE_KEY: &str = "bonsai.exclude";
 
-    if config_path.exists() && !force {
+pub fn exec(git: &GitRunner, args: InitArgs, no_color: bool) -> Result<()> {
+    if git.config_path.exists() && !args.force {
         return Err(BonsaiError::AlreadyInitialized);
     }
 
-    // Create .bonsai directory
-    std::fs::create_dir_all(&worktree_dir)?;
+    if let Some(ref name) = args.template {
+        let content = read_template(&template_path(name)?)?;
+        let config = install_config(git, content)?;
+        return prepare(git, &config, args.exclude_mode, no_color);
+    }
+
+    let mut config = Config::default_config();
+    if git.bare {
+        config.defaults.worktree_dir = BareLayout::of(&git.work_dir).worktree_dir;
+    }
+
+    let suggestions = detect::suggest_hooks(git);
+    if !suggestions.is_empty() && accept(&suggestions, args.yes, no_color)? {
+        config.hooks.post_create = suggestions.into_iter().map(|s| s.hook).collect();
+    }
 
     // Write default config
-    let config = Config::default_config();
-    config.save(&config_path)?;
+    config.save(&git.config_path)?;
+
+    prepare(git, &config, args.exclude_mode, no_color)
+}
+
+/// Show the proposed hooks and ask whether to add them. Without a terminal
+/// to ask on, only `--yes` adds them.
+fn accept(suggestions: &[Suggestion], yes: bool, no_color: bool) -> Result<bool> {
+    eprintln!("Suggested post_create hooks:");
+    for s in suggestions {
+        if no_color {
+            eprintln!("  - {} ({})", describe(&s.hook), s.reason);
+        } else {
+            eprintln!(
+                "  - {} {}",
+                describe(&s.hook).cyan(),
+                format!("({})", s.reason).bright_black()
+            );
+        }
+    }
 
-    // Update .gitignore
-    update_gitignore(repo_root)?;
+    if yes {
+        return Ok(true);
+    }
+    if !io::stdin().is_terminal() {
+        eprintln!("Not added; run `bonsai init --force --yes` to add them.");
+        return Ok(false);
+    }
+    eprint!("Add these hooks? [Y/n] ");
+    io::stderr().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim();
+    Ok(input.is_empty() || input.eq_ignore_ascii_case("y"))
+}
+
+fn describe(hook: &Hook) -> String {
+    match hook {
+        Hook::Copy { from, to } if from == to => format!("copy {from}"),
+        Hook::Copy { from, to } => format!("copy {from} -> {to}"),
+        Hook::Symlink { from, to } => format!("symlink {from} -> {to}"),
+        Hook::Command { command, .. } => format!("run `{command}`"),
+    }
+}
+
+/// A template path, or a name resolved to `<name>.toml` in
+/// `$XDG_CONFIG_HOME/bonsai/templates` (`~/.config/bonsai/templates`).
+fn template_path(name: &str) -> Result<PathBuf> {
+    let as_path = Path::new(name);
+    if name.contains(std::path::MAIN_SEPARATOR) || name.ends_with(".toml") || as_path.is_file() {
+        return Ok(as_path.to_path_buf());
+    }
+
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok_or_else(|| BonsaiError::Config("cannot locate the template directory".to_string()))?;
+    let dir = config_home.join("bonsai/templates");
+    let path = dir.join(format!("{name}.toml"));
+    if !path.is_file() {
+        return Err(BonsaiError::Config(format!(
+            "no template named `{name}` in {}",
+            dir.display()
+        )));
+    }
+    Ok(path)
+}
+
+/// Contents of a config template, checked before anything is written.
+pub(crate) fn read_template(path: &Path) -> Result<String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| BonsaiError::Config(format!("cannot read {}: {e}", path.display())))?;
+    Config::parse(&content, path)?;
+    Ok(content)
+}
+
+/// Write config text as is, keeping its comments. A bare layout keeps its
+/// own `worktree_dir`, since a shared template cannot know the layout.
+pub(crate) fn install_config(git: &GitRunner, content: String) -> Result<Config> {
+    std::fs::write(&git.config_path, content)?;
+    let mut config = Config::load(&git.config_path)?;
+    if git.bare {
+        config.defaults.worktree_dir = BareLayout::of(&git.work_dir).worktree_dir;
+        config.save(&git.config_path)?;
+    }
+    Ok(config)
+}
+
+/// Create the worktree directory for a config that is already in place and
+/// keep it out of git.
+pub(crate) fn prepare(
+    git: &GitRunner,
+    config: &Config,
+    exclude_mode: ExcludeMode,
+    no_color: bool,
+) -> Result<()> {
+    let repo_root = &git.repo_root;
+    let worktree_dir = config.defaults.worktree_dir.trim_end_matches('/');
+
+    // Create the worktree directory
+    std::fs::create_dir_all(repo_root.join(worktree_dir))?;
+
+    // A bare repository has no working tree to ignore worktrees in
+    let inside_repo = !Path::new(worktree_dir).is_absolute() && !worktree_dir.starts_with("..");
+    if !git.bare && inside_repo {
+        let entry = format!("{worktree_dir}/");
+        let added = match exclude_mode {
+            ExcludeMode::Gitignore => append_line(&repo_root.join(".gitignore"), &entry)?,
+            ExcludeMode::InfoExclude => append_line(&info_exclude(git)?, &entry)?,
+            ExcludeMode::None => true,
+        };
+        if added {
+            let mode = exclude_mode
+                .to_possible_value()
+                .map(|v| v.get_name().to_string())
+                .unwrap_or_default();
+            git.run(&["config", EXCLUDE_KEY, &mode])?;
+        }
+    }
 
     if no_color {
         eprintln!("Initialized bonsai in {}", repo_root.display());
@@ -36,20 +178,54 @@ pub fn exec(repo_root: &Path, force: bool, no_color: bool) -> Result<()> {
     Ok(())
 }
 
-fn update_gitignore(repo_root: &Path) -> Result<()> {
-    let gitignore_path = repo_root.join(".gitignore");
-    let entry = ".bonsai/";
+/// `info/exclude` in the common git dir, shared by all worktrees.
+pub(crate) fn info_exclude(git: &GitRunner) -> Result<PathBuf> {
+    Ok(git.git_common_dir()?.join("info").join("exclude"))
+}
+
+pub(crate) fn update_gitignore(repo_root: &Path, entry: &str) -> Result<()> {
+    append_line(&repo_root.join(".gitignore"), entry)?;
+    Ok(())
+}
 
-    if gitignore_path.exists() {
-        let content = std::fs::read_to_string(&gitignore_path)?;
+/// Add `entry` as a line of an ignore file unless it is there already.
+/// Returns whether the file changed.
+fn append_line(path: &Path, entry: &str) -> Result<bool> {
+    if path.exists() {
+        let content = std::fs::read_to_string(path)?;
         if content.lines().any(|line| line.trim() == entry) {
-            return Ok(());
+            return Ok(false);
         }
-        let separator = if content.ends_with('\n') { "" } else { "\n" };
-        std::fs::write(&gitignore_path, format!("{content}{separator}{entry}\n"))?;
+        let separator = if content.is_empty() || content.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        std::fs::write(path, format!("{content}{separator}{entry}\n"))?;
     } else {
-        std::fs::write(&gitignore_path, format!("{entry}\n"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, format!("{entry}\n"))?;
     }
 
-    Ok(())
+    Ok(true)
+}
+
+/// Remove `entry` lines from an ignore file. Returns whether the file is
+/// now empty.
+pub(crate) fn remove_line(path: &Path, entry: &str) -> Result<bool> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Ok(false);
+    };
+    let kept: Vec<&str> = content
+        .lines()
+        .filter(|line| line.trim() != entry)
+        .collect();
+    if kept.is_empty() {
+        std::fs::write(path, "")?;
+        return Ok(true);
+    }
+    std::fs::write(path, format!("{}\n", kept.join("\n")))?;
+    Ok(false)
 }
@@ -1,5 +1,6 @@
 use colored::Colorize;
 
+use crate::config::Config;
 use crate::error::Result;
 use crate::git::runner::GitRunner;
 
@@ -32,11 +33,16 @@ pub fn exec(
 
     // Table display
     let current_dir = std::env::current_dir().ok();
+    let config = Config::load_if_present(&git.config_path)?;
+    let configured_base = config
+        .as_ref()
+        .and_then(|c| c.defaults.base_branch.as_deref());
 
     // Collect data
     struct Row {
         is_current: bool,
         branch: String,
+        issue: String,
         path: String,
         status: String,
     }
@@ -49,6 +55,11 @@ pub fn exec(
             .unwrap_or(false);
 
         let branch = wt.branch.as_deref().unwrap_or("(detached)").to_string();
+        let issue = wt
+            .branch
+            .as_deref()
+            .and_then(|b| git.branch_issue(b))
+            .unwrap_or_default();
 
         let path = if wt.is_main {
             wt.path.display().to_string()
@@ -61,9 +72,13 @@ pub fn exec(
         };
 
         let status = if show_status {
-            match git.status_summary(&wt.path) {
+            let summary = match git.status_summary(&wt.path) {
                 Ok(s) => s.short_display(),
                 Err(_) => "?".to_string(),
+            };
+            match git.worktree_base_comparison(wt, configured_base) {
+                Some(cmp) => format!("{summary} [{}]", cmp.short_display()),
+                None => summary,
             }
         } else {
             String::new()
@@ -72,6 +87,7 @@ pub fn exec(
         rows.push(Row {
             is_current,
             branch,
+            issue,
             path,
             status,
         });
@@ -85,12 +101,26 @@ pub fn exec(
         .unwrap_or(6)
         .max(6);
     let path_width = rows.iter().map(|r| r.path.len()).max().unwrap_or(4).max(4);
+    // The issue column only appears once a branch has an issue recorded
+    let issue_width = match rows.iter().map(|r| r.issue.chars().count()).max() {
+        None | Some(0) => 0,
+        Some(width) => width.max(5),
+    };
+    let issue_column = |issue: &str| {
+        if issue_width == 0 {
+            String::new()
+        } else {
+            format!("{issue:<issue_width$}  ")
+        }
+    };
 
     // Header
     if show_status {
         let header = format!(
-            "  {:<branch_width$}  {:<path_width$}  STATUS",
-            "BRANCH", "PATH"
+            "  {:<branch_width$}  {}{:<path_width$}  STATUS",
+            "BRANCH",
+            issue_column("ISSUE"),
+            "PATH"
         );
         let separator_len = header.len();
         if no_color {
@@ -101,7 +131,11 @@ pub fn exec(
             println!("{}", "─".repeat(separator_len).bright_black());
         }
     } else {
-        let header = format!("  {:<branch_width$}  PATH", "BRANCH");
+        let header = format!(
+            "  {:<branch_width$}  {}PATH",
+            "BRANCH",
+            issue_column("ISSUE")
+        );
         let separator_len = header.len();
         if no_color {
             println!("{header}");
@@ -115,15 +149,19 @@ pub fn exec(
     // Rows
     for row in &rows {
         let marker = if row.is_current { "*" } else { " " };
+        let issue = issue_column(&row.issue);
 
         if no_color {
             if show_status {
                 println!(
-                    "{marker} {:<branch_width$}  {:<path_width$}  {}",
+                    "{marker} {:<branch_width$}  {issue}{:<path_width$}  {}",
                     row.branch, row.path, row.status
                 );
             } else {
-                println!("{marker} {:<branch_width$}  {}", row.branch, row.path);
+                println!(
+                    "{marker} {:<branch_width$}  {issue}{}",
+                    row.branch, row.path
+                );
             }
         } else {
             let marker_display = if row.is_current {
@@ -133,19 +171,20 @@ pub fn exec(
             };
             let branch_display = row.branch.cyan().to_string();
             let path_display = row.path.yellow().to_string();
+            let issue_display = issue.magenta().to_string();
 
             if show_status {
                 // Pad branch with spaces to align (accounting for ANSI codes)
                 let branch_padding = branch_width.saturating_sub(row.branch.len());
                 let path_padding = path_width.saturating_sub(row.path.len());
                 println!(
-                    "{marker_display} {branch_display}{:branch_padding$}  {path_display}{:path_padding$}  {}",
+                    "{marker_display} {branch_display}{:branch_padding$}  {issue_display}{path_display}{:path_padding$}  {}",
                     "", "", row.status
                 );
             } else {
                 let branch_padding = branch_width.saturating_sub(row.branch.len());
                 println!(
-                    "{marker_display} {branch_display}{:branch_padding$}  {path_display}",
+                    "{marker_display} {branch_display}{:branch_padding$}  {issue_display}{path_display}",
                     ""
                 );
             }
@@ -1,12 +1,23 @@
 pub mod add;
+pub mod adopt;
 pub mod cd;
+pub mod clone;
 pub mod completion;
+pub mod config;
+pub mod deinit;
+pub mod doctor;
+pub mod exec;
 pub mod init;
 pub mod list;
 pub mod lock;
 pub mod move_wt;
+pub mod prompt;
 pub mod prune;
 pub mod remove;
 pub mod rename;
 pub mod shell_init;
 pub mod status;
+pub mod switch;
+pub mod sync;
+pub mod trash;
+pub mod undo;
@@ -1,11 +1,19 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use colored::Colorize;
 
+use crate::directive;
 use crate::error::{BonsaiError, Result};
 use crate::git::runner::GitRunner;
+use crate::journal::{Journal, Step};
 
-pub fn exec(git: &GitRunner, worktree: &str, new_path: &str, no_color: bool) -> Result<()> {
+pub fn exec(
+    git: &GitRunner,
+    cwd: &Path,
+    worktree: &str,
+    new_path: &str,
+    no_color: bool,
+) -> Result<()> {
     let worktrees = git.worktree_list()?;
 
     let wt = worktrees
@@ -24,8 +32,23 @@ pub fn exec(git: &GitRunner, worktree: &str, new_path: &str, no_color: bool) ->
         });
     }
 
-    let target = PathBuf::from(new_path);
-    git.worktree_move(&wt.path, &target)?;
+    // Journaled as absolute, since undo may run from another directory
+    let target = cwd.join(new_path);
+    let rel = directive::cwd_within(&wt.path);
+    let mut journal = Journal::begin(git, &format!("move {worktree} {new_path}"))?;
+    journal.step(
+        Step::WorktreeMove {
+            from: wt.path.clone(),
+            to: target.clone(),
+        },
+        || git.worktree_move(&wt.path, &target),
+        no_color,
+    )?;
+    journal.finish()?;
+    if !git.dry_run {
+        let target = std::fs::canonicalize(&target).unwrap_or_else(|_| target.clone());
+        directive::follow(rel, &target)?;
+    }
 
     if no_color {
         eprintln!(
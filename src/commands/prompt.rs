@@ -0,0 +1,129 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, Prompt};
+use crate::error::Result;
+use crate::git::runner::{BareLayout, GitRunner};
+use crate::trash;
+
+/// Linked worktree containing the current directory, found by reading the
+/// `.git` file and admin dir directly so no git process is spawned.
+struct Location {
+    root: PathBuf,
+    git_dir: PathBuf,
+    common_dir: PathBuf,
+}
+
+pub fn exec(cwd: &Path, format: Option<&str>, with_status: bool) -> Result<()> {
+    // A prompt must never fail; outside a linked worktree it is simply empty
+    let Some(loc) = locate(cwd) else {
+        return Ok(());
+    };
+
+    let settings = config_path(&loc.common_dir)
+        .and_then(|path| Config::load(&path).ok())
+        .map(|c| c.prompt)
+        .unwrap_or_default();
+    let template = format.unwrap_or(&settings.template);
+
+    let status = if with_status && template.contains("{status}") {
+        cached_status(&loc, &settings)
+    } else {
+        String::new()
+    };
+
+    let segment = render(template, &loc, &status);
+    if !segment.is_empty() {
+        println!("{segment}");
+    }
+    Ok(())
+}
+
+fn locate(cwd: &Path) -> Option<Location> {
+    let root = cwd.ancestors().find(|dir| dir.join(".git").exists())?;
+    let dot_git = root.join(".git");
+
+    // Only linked worktrees have a `.git` file; the main worktree has a directory
+    let content = std::fs::read_to_string(&dot_git).ok()?;
+    let git_dir = root.join(content.trim().strip_prefix("gitdir: ")?);
+
+    // The `.git` file next to a bare repo points at the repo, not a worktree
+    let common = std::fs::read_to_string(git_dir.join("commondir")).ok()?;
+    let common_dir = git_dir.join(common.trim());
+
+    Some(Location {
+        root: root.to_path_buf(),
+        git_dir,
+        common_dir,
+    })
+}
+
+/// `.bonsai.toml` for the repository, telling bare repositories apart by
+/// reading their config file rather than asking git.
+fn config_path(common_dir: &Path) -> Option<PathBuf> {
+    let common_dir = std::fs::canonicalize(common_dir).ok()?;
+    let git_config = std::fs::read_to_string(common_dir.join("config")).unwrap_or_default();
+    let bare = git_config
+        .lines()
+        .any(|l| l.split_whitespace().collect::<String>() == "bare=true");
+    if bare {
+        Some(BareLayout::of(&common_dir).config_path)
+    } else {
+        Some(common_dir.parent()?.join(".bonsai.toml"))
+    }
+}
+
+fn render(template: &str, loc: &Location, status: &str) -> String {
+    let head = std::fs::read_to_string(loc.git_dir.join("HEAD")).unwrap_or_default();
+    let head = head.trim();
+    let name = match head.strip_prefix("ref: refs/heads/") {
+        Some(branch) => branch.to_string(),
+        None => head.chars().take(7).collect(),
+    };
+    let dir = loc
+        .root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let lock = if loc.git_dir.join("locked").exists() {
+        "🔒"
+    } else {
+        ""
+    };
+
+    let rendered = template
+        .replace("{name}", &name)
+        .replace("{dir}", &dir)
+        .replace("{status}", status)
+        .replace("{lock}", lock);
+    rendered.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Status display for the worktree, reused from the cache while it is
+/// younger than the configured TTL.
+fn cached_status(loc: &Location, settings: &Prompt) -> String {
+    let cache = loc.git_dir.join("bonsai-prompt");
+    let now = trash::now();
+
+    if let Ok(content) = std::fs::read_to_string(&cache) {
+        if let Some((stamp, status)) = content.split_once('\n') {
+            let fresh = stamp
+                .parse::<u64>()
+                .is_ok_and(|t| now.saturating_sub(t) < settings.status_ttl);
+            if fresh {
+                return status.trim_end().to_string();
+            }
+        }
+    }
+
+    let git = GitRunner::new(loc.root.clone(), false, false, true);
+    let status = match git.status_summary(&loc.root) {
+        // Nothing to show, not even "clean", keeps the segment short
+        Ok(summary) => match summary.short_display() {
+            s if s == "clean" => String::new(),
+            s => s,
+        },
+        Err(_) => return String::new(),
+    };
+    let _ = std::fs::write(&cache, format!("{now}\n{status}\n"));
+    status
+}
@@ -2,28 +2,51 @@ use std::io::{self, Write};
 
 use colored::Colorize;
 
+use crate::config::Config;
+use crate::directive;
 use crate::error::Result;
 use crate::git::runner::GitRunner;
 use crate::git::worktree::WorktreeInfo;
+use crate::journal::{Journal, Step};
+use crate::safety;
+
+pub struct PruneArgs {
+    pub merged: Option<Option<String>>,
+    pub stale: Option<u64>,
+    pub with_branch: bool,
+    pub interactive: bool,
+    pub yes: bool,
+    pub discard_unpushed: bool,
+}
+
+pub fn exec(git: &GitRunner, args: PruneArgs, no_color: bool) -> Result<()> {
+    let PruneArgs {
+        merged,
+        stale: _,
+        with_branch,
+        interactive: _,
+        yes,
+        discard_unpushed,
+    } = args;
 
-pub fn exec(
-    git: &GitRunner,
-    merged: Option<Option<String>>,
-    _stale: Option<u64>,
-    with_branch: bool,
-    _interactive: bool,
-    yes: bool,
-    no_color: bool,
-) -> Result<()> {
     // First, run git worktree prune to clean up stale entries
     git.worktree_prune()?;
 
     let worktrees = git.worktree_list()?;
+    let config = Config::load_if_present(&git.config_path)?;
+    let configured_base = config
+        .as_ref()
+        .and_then(|c| c.defaults.base_branch.as_deref());
 
     let mut targets: Vec<&WorktreeInfo> = Vec::new();
 
     if let Some(base_opt) = merged {
-        let base = base_opt.unwrap_or_else(|| detect_default_branch(git));
+        let base = match base_opt {
+            Some(base) => base,
+            None => git
+                .default_branch(configured_base)
+                .unwrap_or_else(|| "master".to_string()),
+        };
 
         for wt in &worktrees {
             if wt.is_main {
@@ -40,6 +63,41 @@ pub fn exec(
         }
     }
 
+    // Keep worktrees that would lose work unless explicitly discarded
+    if !discard_unpushed {
+        let mut kept = false;
+        targets.retain(|wt| {
+            let name = wt.branch.as_deref().unwrap_or("(detached)");
+            let settings = config
+                .as_ref()
+                .map(|c| c.for_worktree(git, wt.branch.as_deref()));
+            let base = settings
+                .as_ref()
+                .and_then(|c| c.defaults.base_branch.as_deref());
+            let protected = settings
+                .as_ref()
+                .map(|c| c.defaults.protected_files.as_slice())
+                .unwrap_or_default();
+            match safety::check(git, wt, with_branch, base, protected) {
+                Ok(report) if report.is_empty() => true,
+                Ok(report) => {
+                    safety::print_report(name, &report, no_color);
+                    kept = true;
+                    false
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Skipping {name}: could not check it for work that would be lost ({e})"
+                    );
+                    false
+                }
+            }
+        });
+        if kept {
+            eprintln!("Skipping the worktrees above (use --discard-unpushed to remove them).");
+        }
+    }
+
     if targets.is_empty() {
         if no_color {
             eprintln!("Nothing to prune.");
@@ -75,9 +133,21 @@ pub fn exec(
         }
     }
 
+    let mut journal = Journal::begin(git, "prune")?;
+
     for wt in &targets {
         let branch_name = wt.branch.clone();
-        git.worktree_remove(&wt.path, false)?;
+        let inside = directive::cwd_within(&wt.path).is_some();
+        journal.step(
+            Step::WorktreeRemove {
+                path: wt.path.clone(),
+                branch: branch_name.clone(),
+                head: wt.head.clone(),
+                trash_id: None,
+            },
+            || git.worktree_remove(&wt.path, discard_unpushed),
+            no_color,
+        )?;
 
         if no_color {
             eprintln!("Removed worktree at {}", wt.path.display());
@@ -89,9 +159,22 @@ pub fn exec(
             );
         }
 
+        if inside && !git.dry_run {
+            let main_path = worktrees.iter().find(|w| w.is_main).map(|w| &w.path);
+            directive::cd(main_path.unwrap_or(&git.repo_root))?;
+        }
+
         if with_branch {
             if let Some(ref branch) = branch_name {
-                git.branch_delete(branch, false)?;
+                let head = git.rev_parse(branch)?;
+                journal.step(
+                    Step::BranchDelete {
+                        name: branch.clone(),
+                        head,
+                    },
+                    || git.branch_delete(branch, discard_unpushed),
+                    no_color,
+                )?;
                 if no_color {
                     eprintln!("Deleted branch {branch}");
                 } else {
@@ -101,14 +184,5 @@ pub fn exec(
         }
     }
 
-    Ok(())
-}
-
-fn detect_default_branch(git: &GitRunner) -> String {
-    // Try main, then master
-    if git.branch_exists("main").unwrap_or(false) {
-        "main".to_string()
-    } else {
-        "master".to_string()
-    }
+    journal.finish()
 }
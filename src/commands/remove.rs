@@ -1,15 +1,31 @@
 use colored::Colorize;
 
+use crate::config::Config;
+use crate::directive;
 use crate::error::{BonsaiError, Result};
 use crate::git::runner::GitRunner;
+use crate::journal::{Journal, Step};
+use crate::safety;
+use crate::trash;
+
+pub struct RemoveArgs {
+    pub worktree: String,
+    pub with_branch: bool,
+    pub force: bool,
+    pub discard_unpushed: bool,
+    pub trash: bool,
+}
+
+pub fn exec(git: &GitRunner, args: RemoveArgs, no_color: bool) -> Result<()> {
+    let RemoveArgs {
+        ref worktree,
+        with_branch,
+        force,
+        discard_unpushed,
+        trash,
+    } = args;
+    let worktree = worktree.as_str();
 
-pub fn exec(
-    git: &GitRunner,
-    worktree: &str,
-    with_branch: bool,
-    force: bool,
-    no_color: bool,
-) -> Result<()> {
     let worktrees = git.worktree_list()?;
 
     let wt = worktrees
@@ -28,10 +44,70 @@ pub fn exec(
         });
     }
 
+    let config = Config::load_if_present(&git.config_path)?
+        .map(|c| c.for_worktree(git, wt.branch.as_deref()));
+    let (configured_base, protected) = match config {
+        Some(ref c) => (
+            c.defaults.base_branch.as_deref(),
+            c.defaults.protected_files.as_slice(),
+        ),
+        None => (None, &[][..]),
+    };
+
+    let mut report = safety::check(git, wt, with_branch, configured_base, protected)?;
+    if trash {
+        report.retain_not_snapshotted();
+    }
+    if !report.is_empty() && !discard_unpushed {
+        safety::print_report(worktree, &report, no_color);
+        // Kept apart from `would_lose_work` so scripts can offer `--trash`
+        if report.is_only_local_changes() {
+            return Err(BonsaiError::DirtyWorktree {
+                path: wt.path.clone(),
+            });
+        }
+        return Err(BonsaiError::WouldLoseWork {
+            name: worktree.to_string(),
+        });
+    }
+
+    let mut trash_id = None;
+    if trash {
+        let entry = trash::snapshot(git, wt, with_branch)?;
+        trash_id = Some(entry.id.clone());
+        if no_color {
+            eprintln!(
+                "Saved snapshot {} (restore with `bonsai restore {}`)",
+                entry.id, entry.id
+            );
+        } else {
+            eprintln!(
+                "{} snapshot {} (restore with `bonsai restore {}`)",
+                "Saved".green(),
+                entry.id.yellow(),
+                entry.id
+            );
+        }
+    }
+
+    // Anything at risk was either absent, snapshotted or explicitly discarded above
+    let force = force || discard_unpushed || trash;
+
     let branch_name = wt.branch.clone();
     let wt_path = wt.path.clone();
+    let inside = directive::cwd_within(&wt_path).is_some();
 
-    git.worktree_remove(&wt_path, force)?;
+    let mut journal = Journal::begin(git, &format!("remove {worktree}"))?;
+    journal.step(
+        Step::WorktreeRemove {
+            path: wt_path.clone(),
+            branch: branch_name.clone(),
+            head: wt.head.clone(),
+            trash_id,
+        },
+        || git.worktree_remove(&wt_path, force),
+        no_color,
+    )?;
 
     if no_color {
         eprintln!("Removed worktree at {}", wt_path.display());
@@ -43,9 +119,23 @@ pub fn exec(
         );
     }
 
+    // Don't leave the shell standing in a deleted directory
+    if inside && !git.dry_run {
+        let main_path = worktrees.iter().find(|w| w.is_main).map(|w| &w.path);
+        directive::cd(main_path.unwrap_or(&git.repo_root))?;
+    }
+
     if with_branch {
         if let Some(ref branch) = branch_name {
-            git.branch_delete(branch, force)?;
+            let head = git.rev_parse(branch)?;
+            journal.step(
+                Step::BranchDelete {
+                    name: branch.clone(),
+                    head,
+                },
+                || git.branch_delete(branch, force),
+                no_color,
+            )?;
             if no_color {
                 eprintln!("Deleted branch {branch}");
             } else {
@@ -54,5 +144,5 @@ pub fn exec(
         }
     }
 
-    Ok(())
+    journal.finish()
 }
@@ -1,8 +1,11 @@
 use colored::Colorize;
 
-use crate::config::Config;
+use crate::config::{BranchPolicy, Config};
+use crate::directive;
 use crate::error::{BonsaiError, Result};
 use crate::git::runner::GitRunner;
+use crate::journal::{Journal, Step};
+use crate::naming;
 
 pub fn exec(git: &GitRunner, old: &str, new: &str, no_color: bool) -> Result<()> {
     let worktrees = git.worktree_list()?;
@@ -20,17 +23,42 @@ pub fn exec(git: &GitRunner, old: &str, new: &str, no_color: bool) -> Result<()>
         });
     }
 
+    let config = Config::load_if_present(&git.config_path)?;
+    let no_policy = BranchPolicy::default();
+    let policy = config.as_ref().map_or(&no_policy, |c| &c.branches);
+    naming::check(git, policy, new)?;
+
+    let mut journal = Journal::begin(git, &format!("rename {old} {new}"))?;
+
     // Rename branch
-    git.branch_rename(old, new)?;
+    journal.step(
+        Step::BranchRename {
+            from: old.to_string(),
+            to: new.to_string(),
+        },
+        || git.branch_rename(old, new),
+        no_color,
+    )?;
 
     // Move worktree directory if under managed dir
-    let config_path = git.repo_root.join(".bonsai.toml");
-    if let Ok(config) = Config::load(&config_path) {
+    if let Some(config) = config {
         let managed_dir = git.repo_root.join(&config.defaults.worktree_dir);
         if wt.path.starts_with(&managed_dir) {
             let new_dir_name = new.replace('/', "-");
             let new_path = managed_dir.join(&new_dir_name);
-            git.worktree_move(&wt.path, &new_path)?;
+            let rel = directive::cwd_within(&wt.path);
+            journal.step(
+                Step::WorktreeMove {
+                    from: wt.path.clone(),
+                    to: new_path.clone(),
+                },
+                || git.worktree_move(&wt.path, &new_path),
+                no_color,
+            )?;
+            journal.finish()?;
+            if !git.dry_run {
+                directive::follow(rel, &new_path)?;
+            }
 
             if no_color {
                 eprintln!("Renamed {old} -> {new}");
@@ -47,6 +75,8 @@ pub fn exec(git: &GitRunner, old: &str, new: &str, no_color: bool) -> Result<()>
         }
     }
 
+    journal.finish()?;
+
     if no_color {
         eprintln!("Renamed {old} -> {new}");
     } else {
@@ -1,18 +1,34 @@
-use clap_complete::Shell;
+use std::io::Write;
 
+use crate::cli::InitShell;
+use crate::commands::completion::BIN_NAMES;
+use crate::complete;
 use crate::error::Result;
 
-pub fn exec(shell: Shell) -> Result<()> {
-    let script = match shell {
-        Shell::Bash => BASH_INIT,
-        Shell::Zsh => ZSH_INIT,
-        Shell::Fish => FISH_INIT,
-        _ => {
-            eprintln!("Unsupported shell for shell-init");
-            return Ok(());
-        }
+pub fn exec(shell: InitShell, prompt: bool) -> Result<()> {
+    let (script, prompt_hook, shell_name) = match shell {
+        InitShell::Bash => (BASH_INIT, BASH_PROMPT, "bash"),
+        InitShell::Zsh => (ZSH_INIT, ZSH_PROMPT, "zsh"),
+        InitShell::Fish => (FISH_INIT, FISH_PROMPT, "fish"),
+        InitShell::PowerShell => (POWERSHELL_INIT, POWERSHELL_PROMPT, "powershell"),
+        InitShell::Elvish => (ELVISH_INIT, ELVISH_PROMPT, "elvish"),
+        InitShell::Nushell => (NUSHELL_INIT, NUSHELL_PROMPT, "nushell"),
     };
-    println!("{script}");
+
+    // Completions call back into this binary, which answers from the clap
+    // definition plus live worktree and branch names
+    let completer = std::env::current_exe()
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| "bonsai".to_string());
+
+    let mut out = std::io::stdout().lock();
+    writeln!(out, "{script}")?;
+    for bin in BIN_NAMES {
+        complete::write_registration(shell_name, bin, &completer, &mut out)?;
+    }
+    if prompt {
+        writeln!(out, "{prompt_hook}")?;
+    }
     Ok(())
 }
 
@@ -26,35 +42,26 @@ bonsai() {
             builtin cd "$dir"
         fi
     else
-        command bonsai "$@"
+        local directive line ret
+        directive="$(mktemp "${TMPDIR:-/tmp}/bonsai.XXXXXX")" || {
+            command bonsai "$@"
+            return
+        }
+        BONSAI_DIRECTIVE_FILE="$directive" command bonsai "$@"
+        ret=$?
+        while IFS= read -r line; do
+            case "$line" in
+                "cd "*) builtin cd -- "${line#cd }" ;;
+            esac
+        done < "$directive"
+        rm -f "$directive"
+        return $ret
     fi
 }
 
 bn() {
     bonsai "$@"
 }
-
-_bonsai_completions() {
-    local cur prev subcmds wt_cmds
-    cur="${COMP_WORDS[COMP_CWORD]}"
-    prev="${COMP_WORDS[COMP_CWORD-1]}"
-    subcmds="init add remove list cd status prune rename move lock unlock completion shell-init"
-    wt_cmds="cd remove status lock unlock rename move"
-
-    if [ "$COMP_CWORD" -eq 1 ]; then
-        COMPREPLY=($(compgen -W "$subcmds" -- "$cur"))
-    elif [ "$COMP_CWORD" -eq 2 ]; then
-        for cmd in $wt_cmds; do
-            if [ "$prev" = "$cmd" ]; then
-                local names
-                names="$(command bonsai list --names-only 2>/dev/null)"
-                COMPREPLY=($(compgen -W "$names" -- "$cur"))
-                return
-            fi
-        done
-    fi
-}
-complete -F _bonsai_completions bonsai bn
 "#;
 
 const ZSH_INIT: &str = r#"
@@ -67,30 +74,26 @@ bonsai() {
             builtin cd "$dir"
         fi
     else
-        command bonsai "$@"
+        local directive line ret
+        directive="$(mktemp "${TMPDIR:-/tmp}/bonsai.XXXXXX")" || {
+            command bonsai "$@"
+            return
+        }
+        BONSAI_DIRECTIVE_FILE="$directive" command bonsai "$@"
+        ret=$?
+        while IFS= read -r line; do
+            case "$line" in
+                "cd "*) builtin cd -- "${line#cd }" ;;
+            esac
+        done < "$directive"
+        rm -f "$directive"
+        return $ret
     fi
 }
 
 bn() {
     bonsai "$@"
 }
-
-_bonsai() {
-    local -a subcmds wt_cmds
-    subcmds=(init add remove list cd status prune rename move lock unlock completion shell-init)
-    wt_cmds=(cd remove status lock unlock rename move)
-
-    if (( CURRENT == 2 )); then
-        _describe 'subcommand' subcmds
-    elif (( CURRENT == 3 )); then
-        if (( ${wt_cmds[(Ie)${words[2]}]} )); then
-            local -a names
-            names=("${(@f)$(command bonsai list --names-only 2>/dev/null)}")
-            _describe 'worktree' names
-        fi
-    fi
-}
-compdef _bonsai bonsai bn
 "#;
 
 const FISH_INIT: &str = r#"
@@ -101,24 +104,191 @@ function bonsai
             builtin cd $dir
         end
     else
-        command bonsai $argv
+        set -l directive (mktemp)
+        or begin
+            command bonsai $argv
+            return
+        end
+        BONSAI_DIRECTIVE_FILE=$directive command bonsai $argv
+        set -l ret $status
+        while read -l line
+            if string match -q 'cd *' -- $line
+                builtin cd (string sub -s 4 -- $line)
+            end
+        end < $directive
+        rm -f $directive
+        return $ret
     end
 end
 
 function bn
     bonsai $argv
 end
+"#;
+
+const POWERSHELL_INIT: &str = r#"
+function bonsai {
+    $exe = Get-Command bonsai -CommandType Application | Select-Object -First 1
+    if ($args.Count -gt 0 -and $args[0] -eq 'cd') {
+        $rest = @($args | Select-Object -Skip 1)
+        $dir = & $exe cd @rest
+        if ($LASTEXITCODE -eq 0 -and $dir) {
+            Set-Location $dir
+        }
+    } else {
+        $directive = [System.IO.Path]::GetTempFileName()
+        $env:BONSAI_DIRECTIVE_FILE = $directive
+        try {
+            & $exe @args
+            $ret = $LASTEXITCODE
+        } finally {
+            Remove-Item Env:\BONSAI_DIRECTIVE_FILE
+        }
+        foreach ($line in Get-Content -LiteralPath $directive) {
+            if ($line.StartsWith('cd ')) {
+                Set-Location -LiteralPath $line.Substring(3)
+            }
+        }
+        Remove-Item -LiteralPath $directive
+        $global:LASTEXITCODE = $ret
+    }
+}
+
+function bn {
+    bonsai @args
+}
+"#;
 
-set -l __bonsai_subcmds init add remove list cd status prune rename move lock unlock completion shell-init
-set -l __bonsai_wt_cmds cd remove status lock unlock rename move
+const ELVISH_INIT: &str = r#"
+use str
 
-complete -c bonsai -f
-complete -c bn -f
-complete -c bonsai -n "not __fish_seen_subcommand_from $__bonsai_subcmds" -a "$__bonsai_subcmds"
-complete -c bn -n "not __fish_seen_subcommand_from $__bonsai_subcmds" -a "$__bonsai_subcmds"
+fn bonsai {|@args|
+    if (and (> (count $args) 0) (eq $args[0] cd)) {
+        var dir = (e:bonsai cd $@args[1..])
+        if (!=s $dir '') {
+            cd $dir
+        }
+    } else {
+        var directive = (e:mktemp)
+        try {
+            tmp E:BONSAI_DIRECTIVE_FILE = $directive
+            e:bonsai $@args
+        } finally {
+            for line [(e:cat $directive)] {
+                if (str:has-prefix $line 'cd ') {
+                    cd $line[3..]
+                }
+            }
+            e:rm -f $directive
+        }
+    }
+}
 
-for cmd in $__bonsai_wt_cmds
-    complete -c bonsai -n "__fish_seen_subcommand_from $cmd" -a "(command bonsai list --names-only 2>/dev/null)"
-    complete -c bn -n "__fish_seen_subcommand_from $cmd" -a "(command bonsai list --names-only 2>/dev/null)"
+fn bn {|@args|
+    bonsai $@args
+}
+"#;
+
+const NUSHELL_INIT: &str = r#"
+def --env --wrapped bonsai [...args: string@"nu-complete bonsai"] {
+    if ($args | length) > 0 and ($args | first) == "cd" {
+        let dir = (^bonsai cd ...($args | skip 1) | str trim)
+        if ($dir | is-not-empty) {
+            cd $dir
+        }
+    } else {
+        let directive = (mktemp -t bonsai.XXXXXX)
+        with-env { BONSAI_DIRECTIVE_FILE: $directive } { ^bonsai ...$args }
+        let targets = (open --raw $directive | lines | where {|l| $l | str starts-with "cd " } | each {|l| $l | str substring 3.. })
+        rm -f $directive
+        if ($targets | is-not-empty) {
+            cd ($targets | last)
+        }
+    }
+}
+
+def --env --wrapped bn [...args: string@"nu-complete bn"] {
+    bonsai ...$args
+}
+"#;
+
+const BASH_PROMPT: &str = r#"
+__bonsai_prompt() {
+    local segment
+    segment="$(command bonsai prompt 2>/dev/null)"
+    BONSAI_PROMPT="${segment:+[$segment] }"
+}
+case "$PROMPT_COMMAND" in
+    *__bonsai_prompt*) ;;
+    *)
+        PROMPT_COMMAND="__bonsai_prompt${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+        PS1='${BONSAI_PROMPT}'"$PS1"
+        ;;
+esac
+"#;
+
+const ZSH_PROMPT: &str = r#"
+__bonsai_prompt() {
+    local segment
+    segment="$(command bonsai prompt 2>/dev/null)"
+    BONSAI_PROMPT="${segment:+[$segment] }"
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook precmd __bonsai_prompt
+setopt PROMPT_SUBST
+if [[ "$PROMPT" != *BONSAI_PROMPT* ]]; then
+    PROMPT='${BONSAI_PROMPT}'"$PROMPT"
+fi
+"#;
+
+const FISH_PROMPT: &str = r#"
+if not functions -q __bonsai_original_fish_prompt
+    functions -q fish_prompt; and functions -c fish_prompt __bonsai_original_fish_prompt
+
+    function fish_prompt
+        set -l segment (command bonsai prompt 2>/dev/null)
+        if test -n "$segment"
+            printf '[%s] ' $segment
+        end
+        if functions -q __bonsai_original_fish_prompt
+            __bonsai_original_fish_prompt
+        end
+    end
 end
 "#;
+
+const POWERSHELL_PROMPT: &str = r#"
+if (-not (Test-Path Function:\__bonsai_original_prompt)) {
+    $function:global:__bonsai_original_prompt = $function:prompt
+    function global:prompt {
+        $exe = Get-Command bonsai -CommandType Application | Select-Object -First 1
+        $segment = & $exe prompt 2>$null
+        $rest = __bonsai_original_prompt
+        if ($segment) { "[$segment] $rest" } else { $rest }
+    }
+}
+"#;
+
+const ELVISH_PROMPT: &str = r#"
+var bonsai-original-prompt = $edit:prompt
+set edit:prompt = {
+    var segment = (str:trim-space (e:bonsai prompt 2>/dev/null | slurp))
+    if (!=s $segment '') {
+        put '['$segment'] '
+    }
+    $bonsai-original-prompt
+}
+"#;
+
+const NUSHELL_PROMPT: &str = r#"
+let bonsai_original_prompt = ($env.PROMPT_COMMAND? | default "")
+$env.PROMPT_COMMAND = {||
+    let segment = (^bonsai prompt | complete | get stdout | str trim)
+    let rest = if ($bonsai_original_prompt | describe) == "closure" {
+        do $bonsai_original_prompt
+    } else {
+        $bonsai_original_prompt
+    }
+    if ($segment | is-empty) { $rest } else { $"[($segment)] ($rest)" }
+}
+"#;
@@ -1,10 +1,16 @@
 use colored::Colorize;
 
+use crate::config::Config;
 use crate::error::{BonsaiError, Result};
 use crate::git::runner::GitRunner;
+use crate::git::StatusSummary;
 
 pub fn exec(git: &GitRunner, worktree: Option<&str>, no_color: bool) -> Result<()> {
     let worktrees = git.worktree_list()?;
+    let config = Config::load_if_present(&git.config_path)?;
+    let configured_base = config
+        .as_ref()
+        .and_then(|c| c.defaults.base_branch.as_deref());
 
     let targets: Vec<_> = if let Some(name) = worktree {
         let wt = worktrees
@@ -48,6 +54,34 @@ pub fn exec(git: &GitRunner, worktree: Option<&str>, no_color: bool) -> Result<(
             println!("  Last commit: {commit_str}");
         }
 
+        if let Some(issue) = wt.branch.as_deref().and_then(|b| git.branch_issue(b)) {
+            if no_color {
+                println!("  Issue: {issue}");
+            } else {
+                println!("  Issue: {}", issue.magenta());
+            }
+        }
+
+        if let Ok(s) = &status {
+            print_details(s, no_color);
+        }
+
+        if let Some(cmp) = git.worktree_base_comparison(wt, configured_base) {
+            println!("  Base: {}", cmp.short_display());
+            if cmp.base_moved() {
+                if no_color {
+                    println!("  Base moved: {} new commit(s) on {}", cmp.behind, cmp.base);
+                } else {
+                    println!(
+                        "  {} {} new commit(s) on {}",
+                        "Base moved:".yellow(),
+                        cmp.behind,
+                        cmp.base.cyan()
+                    );
+                }
+            }
+        }
+
         if wt.is_locked {
             let reason = wt
                 .lock_reason
@@ -66,3 +100,29 @@ pub fn exec(git: &GitRunner, worktree: Option<&str>, no_color: bool) -> Result<(
 
     Ok(())
 }
+
+fn print_details(s: &StatusSummary, no_color: bool) {
+    if let Some(op) = s.operation {
+        if no_color {
+            println!("  In progress: {}", op.label());
+        } else {
+            println!("  {} {}", "In progress:".yellow(), op.label());
+        }
+    }
+    if s.conflicted > 0 {
+        if no_color {
+            println!("  Conflicts: {}", s.conflicted);
+        } else {
+            println!("  {} {}", "Conflicts:".red(), s.conflicted);
+        }
+    }
+    if s.staged > 0 || s.unstaged > 0 {
+        println!("  Staged: {}, Unstaged: {}", s.staged, s.unstaged);
+    }
+    if s.submodules > 0 {
+        println!("  Submodules changed: {}", s.submodules);
+    }
+    if s.stashes > 0 {
+        println!("  Stashes: {}", s.stashes);
+    }
+}
@@ -0,0 +1,25 @@
+use colored::Colorize;
+
+use crate::commands::cd;
+use crate::directive;
+use crate::error::Result;
+use crate::git::runner::GitRunner;
+
+pub fn exec(git: &GitRunner, worktree: &str, no_color: bool) -> Result<()> {
+    let path = cd::resolve(git, worktree)?;
+    if !directive::cd(&path)? {
+        print_no_shell_hint(no_color);
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+/// Explain why the directory did not change when run outside the wrapper.
+pub(crate) fn print_no_shell_hint(no_color: bool) {
+    let hint = "Shell integration is not active; add `eval \"$(bonsai shell-init <shell>)\"` to your shell config to change directory.";
+    if no_color {
+        eprintln!("{hint}");
+    } else {
+        eprintln!("{}", hint.bright_black());
+    }
+}
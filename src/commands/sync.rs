@@ -0,0 +1,236 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use colored::Colorize;
+
+use crate::config::{Config, SyncStrategy};
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+use crate::git::worktree::WorktreeInfo;
+
+pub struct SyncArgs {
+    pub worktrees: Vec<String>,
+    pub all: bool,
+    pub rebase: bool,
+    pub no_fetch: bool,
+    pub jobs: Option<usize>,
+}
+
+enum Outcome {
+    Updated { target: String, commits: usize },
+    UpToDate { target: String },
+    Skipped(String),
+    Failed(String),
+}
+
+impl Outcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Outcome::Updated { .. } => "updated",
+            Outcome::UpToDate { .. } => "up-to-date",
+            Outcome::Skipped(_) => "skipped",
+            Outcome::Failed(_) => "failed",
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            Outcome::Updated { target, commits } => format!("{commits} commit(s) from {target}"),
+            Outcome::UpToDate { target } => target.clone(),
+            Outcome::Skipped(reason) | Outcome::Failed(reason) => reason.clone(),
+        }
+    }
+}
+
+pub fn exec(git: &GitRunner, args: SyncArgs, no_color: bool) -> Result<()> {
+    let config = Config::load_if_present(&git.config_path)?;
+
+    let worktrees = git.worktree_list()?;
+
+    let targets: Vec<&WorktreeInfo> = if args.all {
+        worktrees.iter().collect()
+    } else {
+        args.worktrees
+            .iter()
+            .map(|name| {
+                worktrees
+                    .iter()
+                    .find(|w| {
+                        w.branch.as_deref() == Some(name.as_str())
+                            || w.path.file_name().map(|n| n.to_string_lossy())
+                                == Some(name.as_str().into())
+                    })
+                    .ok_or_else(|| BonsaiError::WorktreeNotFound { name: name.clone() })
+            })
+            .collect::<Result<_>>()?
+    };
+
+    if !args.no_fetch {
+        git.fetch_all()?;
+    }
+
+    let jobs = args
+        .jobs
+        .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1)
+        .clamp(1, targets.len().max(1));
+
+    // Work queue shared by a fixed pool of threads; results keep input order
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Outcome>>> =
+        Mutex::new((0..targets.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(wt) = targets.get(i) else {
+                    break;
+                };
+                // Profiles can set the base and strategy per branch
+                let settings = config
+                    .as_ref()
+                    .map(|c| c.for_worktree(git, wt.branch.as_deref()));
+                let configured_base = settings
+                    .as_ref()
+                    .and_then(|c| c.defaults.base_branch.as_deref());
+                let strategy = if args.rebase {
+                    SyncStrategy::Rebase
+                } else {
+                    settings
+                        .as_ref()
+                        .map(|c| c.defaults.sync_strategy)
+                        .unwrap_or_default()
+                };
+                let outcome = sync_worktree(git, wt, configured_base, strategy);
+                results.lock().unwrap()[i] = Some(outcome);
+            });
+        }
+    });
+
+    let results: Vec<Outcome> = results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|o| o.unwrap_or_else(|| Outcome::Failed("not run".to_string())))
+        .collect();
+
+    print_table(&targets, &results, no_color);
+
+    let failed = results
+        .iter()
+        .filter(|o| matches!(o, Outcome::Failed(_)))
+        .count();
+    if failed > 0 {
+        return Err(BonsaiError::SyncFailed { count: failed });
+    }
+
+    Ok(())
+}
+
+fn sync_worktree(
+    git: &GitRunner,
+    wt: &WorktreeInfo,
+    configured_base: Option<&str>,
+    strategy: SyncStrategy,
+) -> Outcome {
+    if wt.is_locked {
+        return Outcome::Skipped("locked".to_string());
+    }
+    let Some(branch) = wt.branch.as_deref() else {
+        return Outcome::Skipped("detached HEAD".to_string());
+    };
+
+    match git.status_summary(&wt.path) {
+        Ok(s) => {
+            if let Some(op) = s.operation {
+                return Outcome::Skipped(format!("{} in progress", op.label()));
+            }
+            if s.modified + s.added + s.deleted + s.conflicted > 0 {
+                return Outcome::Skipped("uncommitted changes".to_string());
+            }
+            if s.untracked > 0 {
+                return Outcome::Skipped("untracked files".to_string());
+            }
+            if s.submodules > 0 {
+                return Outcome::Skipped("submodule changes".to_string());
+            }
+        }
+        Err(_) => return Outcome::Failed("status unavailable".to_string()),
+    }
+
+    let target = match git
+        .upstream(&wt.path)
+        .or_else(|| git.base_branch_for(branch, configured_base))
+    {
+        Some(t) if t != branch => t,
+        _ => return Outcome::Skipped("no upstream or base".to_string()),
+    };
+
+    let commits = match git.rev_count(&wt.path, &format!("HEAD..{target}")) {
+        Ok(n) => n,
+        Err(_) => return Outcome::Failed(format!("cannot resolve {target}")),
+    };
+    if commits == 0 {
+        return Outcome::UpToDate { target };
+    }
+
+    let result = match strategy {
+        SyncStrategy::FastForward => git
+            .merge_ff_only(&wt.path, &target)
+            .map_err(|_| format!("diverged from {target}, cannot fast-forward")),
+        SyncStrategy::Rebase => git
+            .rebase_or_abort(&wt.path, &target)
+            .map_err(|_| format!("conflicts rebasing onto {target}, rebase aborted")),
+    };
+
+    match result {
+        Ok(()) => Outcome::Updated { target, commits },
+        Err(reason) => Outcome::Failed(reason),
+    }
+}
+
+fn print_table(targets: &[&WorktreeInfo], results: &[Outcome], no_color: bool) {
+    let branches: Vec<&str> = targets
+        .iter()
+        .map(|wt| wt.branch.as_deref().unwrap_or("(detached)"))
+        .collect();
+
+    let branch_width = branches.iter().map(|b| b.len()).max().unwrap_or(6).max(6);
+    let result_width = 10;
+
+    let header = format!(
+        "{:<branch_width$}  {:<result_width$}  DETAIL",
+        "BRANCH", "RESULT"
+    );
+    if no_color {
+        println!("{header}");
+        println!("{}", "─".repeat(header.len()));
+    } else {
+        println!("{}", header.blue().bold());
+        println!("{}", "─".repeat(header.len()).bright_black());
+    }
+
+    for (branch, outcome) in branches.iter().zip(results) {
+        let label = outcome.label();
+        let detail = outcome.detail();
+        if no_color {
+            println!("{branch:<branch_width$}  {label:<result_width$}  {detail}");
+        } else {
+            let branch_padding = branch_width.saturating_sub(branch.len());
+            let label_padding = result_width.saturating_sub(label.len());
+            let label_display = match outcome {
+                Outcome::Updated { .. } => label.green(),
+                Outcome::UpToDate { .. } => label.bright_black(),
+                Outcome::Skipped(_) => label.yellow(),
+                Outcome::Failed(_) => label.red(),
+            };
+            println!(
+                "{}{:branch_padding$}  {label_display}{:label_padding$}  {detail}",
+                branch.cyan(),
+                "",
+                ""
+            );
+        }
+    }
+}
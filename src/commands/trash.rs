@@ -0,0 +1,164 @@
+use colored::Colorize;
+
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+use crate::git::worktree::AddOptions;
+use crate::trash;
+
+pub fn exec_list(git: &GitRunner, no_color: bool) -> Result<()> {
+    let entries = trash::list(git)?;
+
+    if entries.is_empty() {
+        if no_color {
+            eprintln!("Trash is empty.");
+        } else {
+            eprintln!("{}", "Trash is empty.".bright_black());
+        }
+        return Ok(());
+    }
+
+    let now = trash::now();
+    let id_width = entries.iter().map(|e| e.id.len()).max().unwrap_or(2).max(2);
+    let branch_width = entries
+        .iter()
+        .map(|e| e.branch.as_deref().unwrap_or("(detached)").len())
+        .max()
+        .unwrap_or(6)
+        .max(6);
+
+    let header = format!(
+        "{:<id_width$}  {:<branch_width$}  {:<10}  CHANGES",
+        "ID", "BRANCH", "REMOVED"
+    );
+    if no_color {
+        println!("{header}");
+        println!("{}", "─".repeat(header.len()));
+    } else {
+        println!("{}", header.blue().bold());
+        println!("{}", "─".repeat(header.len()).bright_black());
+    }
+
+    for entry in &entries {
+        let branch = entry.branch.as_deref().unwrap_or("(detached)");
+        let age = trash::format_age(now.saturating_sub(entry.removed_at));
+        let changes = if entry.changes.is_some() { "yes" } else { "no" };
+        if no_color {
+            println!(
+                "{:<id_width$}  {branch:<branch_width$}  {age:<10}  {changes}",
+                entry.id
+            );
+        } else {
+            let branch_padding = branch_width.saturating_sub(branch.len());
+            println!(
+                "{:<id_width$}  {}{:branch_padding$}  {age:<10}  {changes}",
+                entry.id,
+                branch.cyan(),
+                ""
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub fn exec_purge(git: &GitRunner, older_than: Option<&str>, no_color: bool) -> Result<()> {
+    let max_age = match older_than {
+        Some(s) => Some(trash::parse_age(s).ok_or_else(|| {
+            BonsaiError::Config(format!(
+                "invalid age '{s}' (expected e.g. 30m, 12h, 7d, 2w)"
+            ))
+        })?),
+        None => None,
+    };
+
+    let now = trash::now();
+    let mut purged = 0;
+    for entry in trash::list(git)? {
+        let age = now.saturating_sub(entry.removed_at);
+        if max_age.is_some_and(|max| age < max) {
+            continue;
+        }
+        trash::discard(git, &entry)?;
+        purged += 1;
+    }
+
+    if no_color {
+        eprintln!(
+            "Purged {purged} trash entr{}",
+            if purged == 1 { "y" } else { "ies" }
+        );
+    } else {
+        eprintln!(
+            "{} {purged} trash entr{}",
+            "Purged".green(),
+            if purged == 1 { "y" } else { "ies" }
+        );
+    }
+
+    Ok(())
+}
+
+pub fn exec_restore(git: &GitRunner, name: &str, no_color: bool) -> Result<()> {
+    let entry = trash::find(git, name)?;
+
+    if entry.path.exists() {
+        return Err(BonsaiError::WorktreeExists {
+            name: entry.path.display().to_string(),
+        });
+    }
+
+    let head = git.rev_parse(&entry.head_ref())?;
+
+    match entry.branch {
+        Some(ref branch) => {
+            if !git.branch_exists(branch)? {
+                git.branch_create(branch, &head)?;
+            } else if entry.branch_deleted && git.rev_parse(branch)? != head {
+                // The branch was recreated since; don't silently attach to it
+                return Err(BonsaiError::BranchExists {
+                    name: branch.clone(),
+                });
+            }
+            let opts = AddOptions {
+                create_branch: false,
+                base: None,
+                detach: false,
+            };
+            git.worktree_add(&entry.path, branch, &opts)?;
+        }
+        None => {
+            let opts = AddOptions {
+                create_branch: false,
+                base: None,
+                detach: true,
+            };
+            git.worktree_add(&entry.path, &head, &opts)?;
+        }
+    }
+
+    if let Some(ref changes) = entry.changes {
+        if let Err(e) = git.apply_snapshot(&entry.path, changes) {
+            // Keep the entry so the changes can still be recovered by hand
+            eprintln!(
+                "Restored worktree, but applying saved changes failed; recover them with `git stash apply {changes}`"
+            );
+            return Err(e);
+        }
+    }
+
+    trash::discard(git, &entry)?;
+
+    let branch = entry.branch.as_deref().unwrap_or("(detached)");
+    if no_color {
+        eprintln!("Restored {branch} at {}", entry.path.display());
+    } else {
+        eprintln!(
+            "{} {} at {}",
+            "Restored".green(),
+            branch.cyan(),
+            entry.path.display().to_string().yellow()
+        );
+    }
+
+    Ok(())
+}
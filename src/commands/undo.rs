@@ -0,0 +1,55 @@
+use std::io::{self, Write};
+
+use colored::Colorize;
+
+use crate::error::Result;
+use crate::git::runner::GitRunner;
+use crate::journal;
+
+pub fn exec(git: &GitRunner, yes: bool, no_color: bool) -> Result<()> {
+    let Some((path, record)) = journal::latest(git)? else {
+        if no_color {
+            eprintln!("Nothing to undo.");
+        } else {
+            eprintln!("{}", "Nothing to undo.".bright_black());
+        }
+        return Ok(());
+    };
+
+    if no_color {
+        eprintln!("Undoing: {}", record.command);
+    } else {
+        eprintln!("{} {}", "Undoing:".green(), record.command.cyan());
+    }
+    for step in record.steps.iter().rev() {
+        eprintln!("  - revert {}", step.describe());
+    }
+
+    // The journal may be old; things could have changed by hand since
+    let mut drift = Vec::new();
+    for step in &record.steps {
+        drift.extend(step.drift(git)?);
+    }
+    if !drift.is_empty() {
+        if no_color {
+            eprintln!("The repository has changed since then:");
+        } else {
+            eprintln!("{}", "The repository has changed since then:".yellow());
+        }
+        for change in &drift {
+            eprintln!("  {change}");
+        }
+        if !yes {
+            eprint!("Undo anyway? [y/N] ");
+            io::stderr().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            if !input.trim().eq_ignore_ascii_case("y") {
+                eprintln!("Aborted.");
+                return Ok(());
+            }
+        }
+    }
+
+    journal::undo(git, &path, record, no_color)
+}
@@ -0,0 +1,176 @@
+use std::ffi::OsString;
+
+use clap::CommandFactory;
+use clap_complete::env::{Bash, Elvish, EnvCompleter, Fish, Powershell, Shells, Zsh};
+use clap_complete::{CompleteEnv, CompletionCandidate};
+
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::git::runner::GitRunner;
+use crate::trash;
+
+/// Environment variable that switches the binary into completion mode.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Shells understood by the completion engine.
+pub const SHELLS: Shells<'static> = Shells(&[&Bash, &Elvish, &Fish, &Nushell, &Powershell, &Zsh]);
+
+/// Answer a completion request from a shell and exit, if this is one.
+pub fn handle_env() {
+    CompleteEnv::with_factory(Cli::command)
+        .var(COMPLETE_VAR)
+        .shells(SHELLS)
+        .complete();
+}
+
+/// Write the completion registration for `bin` in `shell`, calling back into
+/// `completer` for candidates.
+pub fn write_registration(
+    shell: &str,
+    bin: &str,
+    completer: &str,
+    buf: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    let shell = SHELLS
+        .completer(shell)
+        .ok_or_else(|| std::io::Error::other(format!("no completion support for {shell}")))?;
+    shell.write_registration(COMPLETE_VAR, bin, bin, completer, buf)
+}
+
+fn repo() -> Option<GitRunner> {
+    let cwd = std::env::current_dir().ok()?;
+    GitRunner::discover(&cwd, false, false, true).ok()
+}
+
+/// Worktrees by branch (or directory name when detached), with their path.
+pub fn worktrees() -> Vec<CompletionCandidate> {
+    let Some(git) = repo() else {
+        return Vec::new();
+    };
+    let Ok(worktrees) = git.worktree_list() else {
+        return Vec::new();
+    };
+    worktrees
+        .iter()
+        .filter_map(|w| {
+            let name = match w.branch {
+                Some(ref branch) => branch.clone(),
+                None => w.path.file_name()?.to_string_lossy().into_owned(),
+            };
+            Some(CompletionCandidate::new(name).help(Some(w.path.display().to_string().into())))
+        })
+        .collect()
+}
+
+/// Worktrees plus `@` for the main worktree.
+pub fn worktrees_or_main() -> Vec<CompletionCandidate> {
+    let mut candidates = vec![CompletionCandidate::new("@").help(Some("main worktree".into()))];
+    candidates.extend(worktrees());
+    candidates
+}
+
+/// Local branches, then remote-tracking branches.
+pub fn branches() -> Vec<CompletionCandidate> {
+    let Some(git) = repo() else {
+        return Vec::new();
+    };
+    let Ok(output) = git.run(&[
+        "for-each-ref",
+        "--format=%(refname)",
+        "refs/heads",
+        "refs/remotes",
+    ]) else {
+        return Vec::new();
+    };
+    output
+        .lines()
+        .filter(|r| !r.ends_with("/HEAD"))
+        .filter_map(|r| {
+            let (name, help) = match r.strip_prefix("refs/heads/") {
+                Some(name) => (name, "local branch"),
+                None => (r.strip_prefix("refs/remotes/")?, "remote branch"),
+            };
+            Some(CompletionCandidate::new(name).help(Some(help.into())))
+        })
+        .collect()
+}
+
+/// Config profiles by name, with the branches they match.
+pub fn profiles() -> Vec<CompletionCandidate> {
+    let Some(git) = repo() else {
+        return Vec::new();
+    };
+    let Ok(config) = Config::load(&git.config_path) else {
+        return Vec::new();
+    };
+    config
+        .profiles
+        .into_iter()
+        .map(|p| CompletionCandidate::new(p.name).help(p.pattern.map(Into::into)))
+        .collect()
+}
+
+/// Trash snapshots by id.
+pub fn trash_entries() -> Vec<CompletionCandidate> {
+    let Some(git) = repo() else {
+        return Vec::new();
+    };
+    trash::list(&git)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| {
+            let help = e.branch.unwrap_or_else(|| "(detached)".to_string());
+            CompletionCandidate::new(e.id).help(Some(help.into()))
+        })
+        .collect()
+}
+
+/// Nushell completion adapter. Registration defines a `nu-complete <bin>`
+/// command for use as a custom completer on the wrapper's arguments.
+pub struct Nushell;
+
+impl EnvCompleter for Nushell {
+    fn name(&self) -> &'static str {
+        "nushell"
+    }
+
+    fn is(&self, name: &str) -> bool {
+        name == "nushell" || name == "nu"
+    }
+
+    fn write_registration(
+        &self,
+        var: &str,
+        name: &str,
+        _bin: &str,
+        completer: &str,
+        buf: &mut dyn std::io::Write,
+    ) -> std::io::Result<()> {
+        let completer = completer.replace('\'', "");
+        writeln!(
+            buf,
+            r#"
+def "nu-complete {name}" [context: string] {{
+    let words = ($context | split row -r '\s+')
+    with-env {{ {var}: "nushell" }} {{
+        ^'{completer}' -- ...$words | complete | get stdout | lines
+    }}
+}}"#
+        )
+    }
+
+    fn write_complete(
+        &self,
+        cmd: &mut clap::Command,
+        args: Vec<OsString>,
+        current_dir: Option<&std::path::Path>,
+        buf: &mut dyn std::io::Write,
+    ) -> std::io::Result<()> {
+        let index = args.len() - 1;
+        let completions = clap_complete::engine::complete(cmd, args, index, current_dir)?;
+        for candidate in completions {
+            writeln!(buf, "{}", candidate.get_value().to_string_lossy())?;
+        }
+        Ok(())
+    }
+}
@@ -1,66 +1,416 @@
 use std::collections::HashMap;
 use std::path::Path;
 
+use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use toml_edit::{DocumentMut, Item, Value};
 
 use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+use crate::pattern::glob_match;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Schema version written by this build. Older files are migrated on load.
+pub const CONFIG_VERSION: i64 = 2;
+
+/// `.bonsai.toml`, the bonsai configuration at the repository root.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
-    pub version: String,
+    /// Schema version of this file. Older files are still read; run
+    /// `bonsai config migrate` to update them.
+    #[schemars(range(min = 1))]
+    pub version: i64,
     #[serde(default)]
     pub defaults: Defaults,
     #[serde(default)]
     pub hooks: Hooks,
+    #[serde(default, skip_serializing_if = "Prompt::is_default")]
+    pub prompt: Prompt,
+    #[serde(default, skip_serializing_if = "CloneSettings::is_default")]
+    pub clone: CloneSettings,
+    #[serde(default, skip_serializing_if = "BranchPolicy::is_default")]
+    pub branches: BranchPolicy,
+    /// Overrides for branches matching a pattern, such as `release/*`. The
+    /// first matching profile applies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<Profile>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct Defaults {
+    /// Directory new worktrees are created in, relative to the repository root.
     pub worktree_dir: String,
+    /// Branch that new branches are created from and merges are checked against.
+    /// Detected from the remote when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Ignored files worth keeping (globs such as `.env*`); `remove` and
+    /// `prune` refuse to delete worktrees containing them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected_files: Vec<String>,
+    /// How `bonsai sync` brings worktrees up to date.
+    #[serde(default)]
+    pub sync_strategy: SyncStrategy,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum SyncStrategy {
+    /// Only move branches that have not diverged.
+    #[default]
+    #[serde(rename = "fast-forward")]
+    FastForward,
+    /// Rebase local commits onto the upstream or base branch.
+    #[serde(rename = "rebase")]
+    Rebase,
 }
 
 impl Default for Defaults {
     fn default() -> Self {
         Self {
             worktree_dir: ".bonsai".to_string(),
+            base_branch: None,
+            protected_files: Vec::new(),
+            sync_strategy: SyncStrategy::default(),
+        }
+    }
+}
+
+/// Settings for `bonsai prompt`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Prompt {
+    /// Segment template; `{name}`, `{dir}`, `{status}` and `{lock}` are
+    /// replaced and empty placeholders collapse.
+    #[serde(default = "Prompt::default_template")]
+    pub template: String,
+    /// Seconds a computed status is reused before git is asked again.
+    #[serde(default = "Prompt::default_status_ttl")]
+    pub status_ttl: u64,
+}
+
+impl Prompt {
+    fn default_template() -> String {
+        "{name} {status} {lock}".to_string()
+    }
+
+    fn default_status_ttl() -> u64 {
+        5
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Default for Prompt {
+    fn default() -> Self {
+        Self {
+            template: Self::default_template(),
+            status_ttl: Self::default_status_ttl(),
+        }
+    }
+}
+
+/// Settings for `bonsai clone`, usually shipped in a team config template.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CloneSettings {
+    /// Branches that get a worktree right after cloning, e.g. `develop`.
+    #[serde(default)]
+    pub branches: Vec<String>,
+}
+
+impl CloneSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How branches are named. The rules apply to branches created by `add -c`
+/// and `rename`; names git itself rejects are refused regardless.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BranchPolicy {
+    /// Regular expression the whole name must match, e.g.
+    /// `^[a-z]+/[A-Z]+-[0-9]+-[a-z0-9-]+$`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Accepted name prefixes, such as `feature/` or `fix/`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prefixes: Vec<String>,
+    /// Longest allowed name, in characters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// Reject names containing uppercase letters.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub lowercase: bool,
+    /// Name generated by `add --from-title` and `--issue`; `{type}`,
+    /// `{issue}` and `{slug}` are replaced and empty placeholders collapse.
+    #[serde(default = "BranchPolicy::default_template")]
+    pub template: String,
+    /// `{type}` when `add --type` is not given.
+    #[serde(default = "BranchPolicy::default_type")]
+    pub default_type: String,
+}
+
+impl BranchPolicy {
+    fn default_template() -> String {
+        "{type}/{issue}-{slug}".to_string()
+    }
+
+    fn default_type() -> String {
+        "feature".to_string()
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Default for BranchPolicy {
+    fn default() -> Self {
+        Self {
+            pattern: None,
+            prefixes: Vec::new(),
+            max_length: None,
+            lowercase: false,
+            template: Self::default_template(),
+            default_type: Self::default_type(),
         }
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct Hooks {
+    /// Run in order after `add` creates a worktree.
     #[serde(default)]
     pub post_create: Vec<Hook>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", deny_unknown_fields)]
 pub enum Hook {
+    /// Copy a file from the main worktree into the new one.
     #[serde(rename = "copy")]
-    Copy { from: String, to: String },
+    Copy {
+        /// Path in the main worktree.
+        from: String,
+        /// Path in the new worktree.
+        to: String,
+    },
+    /// Symlink a file in the main worktree from the new one.
     #[serde(rename = "symlink")]
-    Symlink { from: String, to: String },
+    Symlink {
+        /// Path in the main worktree.
+        from: String,
+        /// Path in the new worktree.
+        to: String,
+    },
+    /// Run a shell command in the new worktree.
     #[serde(rename = "command")]
     Command {
         command: String,
-        #[serde(default)]
+        /// Extra environment variables for the command.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
         env: HashMap<String, String>,
     },
 }
 
+/// Settings for a group of branches, layered over `defaults` and `hooks`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Name for `bonsai add --profile`.
+    pub name: String,
+    /// Branch glob the profile applies to; `*` also matches `/`. Without
+    /// it the profile is only used when selected by name.
+    #[serde(rename = "match", default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "ProfileDefaults::is_empty")]
+    pub defaults: ProfileDefaults,
+    #[serde(default, skip_serializing_if = "ProfileHooks::is_empty")]
+    pub hooks: ProfileHooks,
+}
+
+/// `defaults` keys a profile replaces. The worktree directory is shared by
+/// all profiles.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileDefaults {
+    /// Replaces `defaults.base_branch`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Replaces `defaults.protected_files`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected_files: Option<Vec<String>>,
+    /// Replaces `defaults.sync_strategy`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_strategy: Option<SyncStrategy>,
+}
+
+impl ProfileDefaults {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileHooks {
+    /// Whether `post_create` runs after the top-level hooks or instead of them.
+    #[serde(default)]
+    pub merge: HookMerge,
+    /// Hooks for the profile's branches, run after `add` creates a worktree.
+    #[serde(default)]
+    pub post_create: Vec<Hook>,
+}
+
+impl ProfileHooks {
+    fn is_empty(&self) -> bool {
+        self.merge == HookMerge::default() && self.post_create.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum HookMerge {
+    /// Run the top-level hooks, then the profile's.
+    #[default]
+    #[serde(rename = "extend")]
+    Extend,
+    /// Run only the profile's hooks.
+    #[serde(rename = "replace")]
+    Replace,
+}
+
 impl Config {
     pub fn default_config() -> Self {
         Self {
-            version: "1".to_string(),
+            version: CONFIG_VERSION,
             defaults: Defaults::default(),
             hooks: Hooks::default(),
+            prompt: Prompt::default(),
+            clone: CloneSettings::default(),
+            branches: BranchPolicy::default(),
+            profiles: Vec::new(),
         }
     }
 
     pub fn load(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path).map_err(|_| BonsaiError::NotInitialized)?;
-        toml::from_str(&content)
-            .map_err(|e| BonsaiError::Config(format!("failed to parse {}: {e}", path.display())))
+        let content = read(path)?;
+        Self::parse(&content, path)
+    }
+
+    /// The config if bonsai is initialized. Unlike a missing file, a file
+    /// that does not parse is an error, so settings such as
+    /// `protected_files` are never skipped silently.
+    pub fn load_if_present(path: &Path) -> Result<Option<Self>> {
+        match Self::load(path) {
+            Ok(config) => Ok(Some(config)),
+            Err(BonsaiError::NotInitialized) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Parse config text, migrating an older schema in memory. `path` is
+    /// only used in error messages.
+    pub fn parse(content: &str, path: &Path) -> Result<Self> {
+        let mut doc = parse_document(content, path)?;
+        let config: Self = if migrate(&mut doc, path)? {
+            let migrated = doc.to_string();
+            toml::from_str(&migrated)
+                .map_err(|e| diagnostic(path, &migrated, e.message(), e.span()))?
+        } else {
+            toml::from_str(content).map_err(|e| diagnostic(path, content, e.message(), e.span()))?
+        };
+
+        for (i, profile) in config.profiles.iter().enumerate() {
+            if config.profiles[..i].iter().any(|p| p.name == profile.name) {
+                return Err(BonsaiError::Config(format!(
+                    "{}: profile `{}` is defined more than once",
+                    path.display(),
+                    profile.name
+                )));
+            }
+        }
+        if let Some(ref pattern) = config.branches.pattern {
+            Regex::new(pattern).map_err(|e| {
+                BonsaiError::Config(format!(
+                    "{}: invalid `branches.pattern`: {e}",
+                    path.display()
+                ))
+            })?;
+        }
+        Ok(config)
+    }
+
+    /// The profile called `name`, or without a name the first one whose
+    /// `match` covers `branch`.
+    pub fn profile(&self, branch: &str, name: Option<&str>) -> Result<Option<&Profile>> {
+        match name {
+            Some(name) => self
+                .profiles
+                .iter()
+                .find(|p| p.name == name)
+                .map(Some)
+                .ok_or_else(|| BonsaiError::Config(format!("no profile named `{name}`"))),
+            None => Ok(self.profiles.iter().find(|p| {
+                p.pattern
+                    .as_deref()
+                    .is_some_and(|pattern| glob_match(pattern, branch))
+            })),
+        }
+    }
+
+    /// `name` if a profile by that name is still defined. A profile recorded
+    /// for a branch may since have been removed from the file.
+    pub fn defined_profile<'a>(&self, name: Option<&'a str>) -> Option<&'a str> {
+        name.filter(|name| self.profiles.iter().any(|p| p.name == *name))
+    }
+
+    /// The settings that apply to `branch`, with its profile folded into
+    /// `defaults` and `hooks`.
+    pub fn for_branch(&self, branch: &str, name: Option<&str>) -> Result<Config> {
+        Ok(self.with_profile(self.profile(branch, name)?))
+    }
+
+    /// The settings for a worktree's branch, honoring the profile recorded
+    /// by `add --profile`.
+    pub fn for_worktree(&self, git: &GitRunner, branch: Option<&str>) -> Config {
+        let profile = branch.and_then(|branch| {
+            let recorded = git.branch_profile(branch);
+            let name = self.defined_profile(recorded.as_deref());
+            self.profile(branch, name).ok().flatten()
+        });
+        self.with_profile(profile)
+    }
+
+    fn with_profile(&self, profile: Option<&Profile>) -> Config {
+        let mut config = self.clone();
+        config.profiles.clear();
+        let Some(profile) = profile else {
+            return config;
+        };
+
+        let overrides = &profile.defaults;
+        if let Some(ref base) = overrides.base_branch {
+            config.defaults.base_branch = Some(base.clone());
+        }
+        if let Some(ref protected) = overrides.protected_files {
+            config.defaults.protected_files = protected.clone();
+        }
+        if let Some(strategy) = overrides.sync_strategy {
+            config.defaults.sync_strategy = strategy;
+        }
+
+        let hooks = profile.hooks.post_create.iter().cloned();
+        match profile.hooks.merge {
+            HookMerge::Extend => config.hooks.post_create.extend(hooks),
+            HookMerge::Replace => config.hooks.post_create = hooks.collect(),
+        }
+        config
     }
 
     pub fn save(&self, path: &Path) -> Result<()> {
@@ -70,3 +420,105 @@ impl Config {
         Ok(())
     }
 }
+
+/// Raw config text; a missing file means bonsai was never initialized.
+pub fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => BonsaiError::NotInitialized,
+        _ => e.into(),
+    })
+}
+
+/// Format-preserving document, for edits that keep comments and layout.
+pub fn parse_document(content: &str, path: &Path) -> Result<DocumentMut> {
+    content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| diagnostic(path, content, e.message(), e.span()))
+}
+
+/// Schema version a config document declares. Version 1 wrote it as a string.
+pub fn schema_version(doc: &DocumentMut, path: &Path) -> Result<i64> {
+    let version = doc.get("version").ok_or_else(|| {
+        BonsaiError::Config(format!(
+            "{}: missing `version` (current schema is {CONFIG_VERSION})",
+            path.display()
+        ))
+    })?;
+    let parsed = match version.as_value() {
+        Some(Value::Integer(n)) => Some(*n.value()),
+        Some(Value::String(s)) => s.value().parse().ok(),
+        _ => None,
+    };
+    parsed.filter(|v| *v >= 1).ok_or_else(|| {
+        BonsaiError::Config(format!(
+            "{}: `version` must be a schema number such as {CONFIG_VERSION}",
+            path.display()
+        ))
+    })
+}
+
+/// Bring `doc` up to [`CONFIG_VERSION`], keeping its formatting. Returns
+/// whether anything changed.
+pub fn migrate(doc: &mut DocumentMut, path: &Path) -> Result<bool> {
+    let version = schema_version(doc, path)?;
+    if version > CONFIG_VERSION {
+        return Err(BonsaiError::Config(format!(
+            "{} uses schema version {version}, but this bonsai only understands up to {CONFIG_VERSION}; upgrade bonsai",
+            path.display()
+        )));
+    }
+    if version == CONFIG_VERSION && doc.get("version").and_then(Item::as_integer).is_some() {
+        return Ok(false);
+    }
+
+    // 1 -> 2: the version became an integer; the layout is otherwise unchanged
+    let decor = doc
+        .get("version")
+        .and_then(Item::as_value)
+        .map(|v| v.decor().clone());
+    let mut value = Value::from(CONFIG_VERSION);
+    if let Some(decor) = decor {
+        *value.decor_mut() = decor;
+    }
+    doc["version"] = Item::Value(value);
+    Ok(true)
+}
+
+/// Error pointing at the offending line, in the style of compiler output:
+///
+/// ```text
+/// .bonsai.toml:7:1: unknown field `post_craete`, expected `post_create`
+///   |
+/// 7 | post_craete = []
+///   | ^^^^^^^^^^^
+/// ```
+fn diagnostic(
+    path: &Path,
+    content: &str,
+    message: &str,
+    span: Option<std::ops::Range<usize>>,
+) -> BonsaiError {
+    let Some(span) = span else {
+        return BonsaiError::Config(format!("{}: {message}", path.display()));
+    };
+    let start = span.start.min(content.len());
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[start..]
+        .find('\n')
+        .map_or(content.len(), |i| start + i);
+    let line_no = content[..start].matches('\n').count() + 1;
+    let column = content[line_start..start].chars().count() + 1;
+    let line = &content[line_start..line_end];
+    let width = content[start..span.end.clamp(start, line_end)]
+        .chars()
+        .count()
+        .max(1);
+
+    let gutter = " ".repeat(line_no.to_string().len());
+    BonsaiError::Config(format!(
+        "{}:{line_no}:{column}: {message}\n{gutter} |\n{line_no} | {line}\n{gutter} | {}{}",
+        path.display(),
+        " ".repeat(column - 1),
+        "^".repeat(width)
+    ))
+}
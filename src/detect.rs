@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::config::Hook;
+use crate::git::runner::GitRunner;
+
+/// A `post_create` hook proposed for the project, with what prompted it.
+pub struct Suggestion {
+    pub reason: String,
+    pub hook: Hook,
+}
+
+/// Hooks that set up a fresh worktree the way the main one is, judged from
+/// the lockfiles and env files in the repository root.
+pub fn suggest_hooks(git: &GitRunner) -> Vec<Suggestion> {
+    let root = &git.repo_root;
+    let has = |name: &str| root.join(name).is_file();
+    let mut suggestions = Vec::new();
+
+    // Ignored env files are missing from new worktrees; tracked ones come with the checkout
+    for name in env_files(root) {
+        if git.is_ignored(&name) {
+            suggestions.push(Suggestion {
+                reason: format!("{name} is not tracked by git"),
+                hook: Hook::Copy {
+                    from: name.clone(),
+                    to: name,
+                },
+            });
+        }
+    }
+
+    if has("package.json") {
+        let (lockfile, command) = [
+            ("pnpm-lock.yaml", "pnpm install"),
+            ("yarn.lock", "yarn install"),
+            ("bun.lock", "bun install"),
+            ("bun.lockb", "bun install"),
+            ("package-lock.json", "npm install"),
+        ]
+        .into_iter()
+        .find(|(lockfile, _)| has(lockfile))
+        .unwrap_or(("package.json", "npm install"));
+        suggestions.push(command_hook(lockfile, command));
+    }
+
+    if has("pyproject.toml") || has("requirements.txt") {
+        if has("uv.lock") {
+            suggestions.push(command_hook("uv.lock", "uv sync"));
+        } else if has("poetry.lock") {
+            suggestions.push(command_hook("poetry.lock", "poetry install"));
+        }
+    }
+
+    if has("Cargo.toml") {
+        suggestions.push(command_hook("Cargo.toml", "cargo fetch"));
+    }
+
+    if has("go.mod") {
+        suggestions.push(command_hook("go.mod", "go mod download"));
+    }
+
+    // direnv refuses to load an `.envrc` in a new directory until it is allowed
+    if has(".envrc") {
+        suggestions.push(command_hook(".envrc", "direnv allow"));
+    }
+
+    suggestions
+}
+
+fn command_hook(found: &str, command: &str) -> Suggestion {
+    Suggestion {
+        reason: format!("found {found}"),
+        hook: Hook::Command {
+            command: command.to_string(),
+            env: HashMap::new(),
+        },
+    }
+}
+
+/// `.env` and `.env.*` files in the repository root, sorted.
+fn env_files(root: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| name == ".env" || name.starts_with(".env."))
+        .collect();
+    names.sort();
+    names
+}
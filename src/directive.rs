@@ -0,0 +1,40 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
+/// File the shell wrapper from `shell-init` reads after each command. Every
+/// line is one instruction; currently only `cd <path>`.
+pub const ENV_VAR: &str = "BONSAI_DIRECTIVE_FILE";
+
+/// Ask the calling shell to change directory. Returns `false` when bonsai was
+/// not run through the shell wrapper.
+pub fn cd(path: &Path) -> Result<bool> {
+    let Some(file) = std::env::var_os(ENV_VAR) else {
+        return Ok(false);
+    };
+    let mut file = std::fs::OpenOptions::new().append(true).open(file)?;
+    writeln!(file, "cd {}", path.display())?;
+    Ok(true)
+}
+
+/// The current directory relative to `root`, if it is inside `root`.
+/// Call before `root` is moved or removed.
+pub fn cwd_within(root: &Path) -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.strip_prefix(root).ok().map(Path::to_path_buf)
+}
+
+/// Follow a worktree moved from under the shell to the same subdirectory in
+/// its new location. `rel` comes from [`cwd_within`] taken before the move.
+pub fn follow(rel: Option<PathBuf>, new_root: &Path) -> Result<()> {
+    if let Some(rel) = rel {
+        let target = new_root.join(rel);
+        if target.is_dir() {
+            cd(&target)?;
+        } else {
+            cd(new_root)?;
+        }
+    }
+    Ok(())
+}
@@ -11,32 +11,269 @@ pub enum BonsaiError {
     #[error("already initialized (use --force to reinitialize)")]
     AlreadyInitialized,
 
+    #[error("destination '{path}' already exists and is not an empty directory")]
+    DestinationExists { path: PathBuf },
+
     #[error("worktree '{name}' already exists")]
     WorktreeExists { name: String },
 
     #[error("worktree '{name}' not found")]
     WorktreeNotFound { name: String },
 
+    #[error("no trash entry matching '{name}'")]
+    TrashEntryNotFound { name: String },
+
     #[error("branch '{name}' not found")]
     BranchNotFound { name: String },
 
     #[error("branch '{name}' already exists")]
     BranchExists { name: String },
 
-    #[error("worktree '{path}' has uncommitted changes (use --force to override)")]
+    #[error("invalid branch name '{name}': {reason}")]
+    InvalidBranchName {
+        name: String,
+        reason: String,
+        suggestion: Option<String>,
+    },
+
+    #[error("worktree '{path}' has uncommitted changes or untracked files (use --trash to keep them, or --discard-unpushed to remove anyway)")]
     DirtyWorktree { path: PathBuf },
 
+    #[error("removing '{name}' would lose work (use --discard-unpushed to proceed)")]
+    WouldLoseWork { name: String },
+
     #[error("git command failed: {command}\n{stderr}")]
     GitCommandFailed { command: String, stderr: String },
 
     #[error("config error: {0}")]
     Config(String),
 
+    #[error("{count} worktree(s) failed to sync")]
+    SyncFailed { count: usize },
+
+    #[error("command failed in {count} worktree(s)")]
+    ExecFailed { count: usize, skipped: usize },
+
     #[error("hook failed: {0}")]
     HookFailed(String),
 
+    #[error("doctor found {count} problem(s) (run `bonsai doctor --fix` to repair)")]
+    DoctorFoundProblems { count: usize },
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, BonsaiError>;
+
+/// Common reasons a git command fails, recognized from its stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitFailureCause {
+    WorktreeLocked,
+    IndexLocked,
+    BranchCheckedOut,
+}
+
+impl GitFailureCause {
+    pub fn classify(stderr: &str) -> Option<Self> {
+        if stderr.contains("locked working tree") {
+            Some(GitFailureCause::WorktreeLocked)
+        } else if stderr.contains("index.lock") {
+            Some(GitFailureCause::IndexLocked)
+        } else if stderr.contains("already checked out")
+            || stderr.contains("already used by worktree")
+            || stderr.contains("checked out at")
+        {
+            Some(GitFailureCause::BranchCheckedOut)
+        } else {
+            None
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GitFailureCause::WorktreeLocked => "worktree_locked",
+            GitFailureCause::IndexLocked => "index_locked",
+            GitFailureCause::BranchCheckedOut => "branch_checked_out",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            GitFailureCause::WorktreeLocked => {
+                "the worktree is locked; run `bonsai unlock <worktree>` first"
+            }
+            GitFailureCause::IndexLocked => {
+                "another git process may be running; if not, delete the index.lock file"
+            }
+            GitFailureCause::BranchCheckedOut => {
+                "the branch is checked out in another worktree; use `bonsai cd` to go there"
+            }
+        }
+    }
+}
+
+impl BonsaiError {
+    /// Stable identifier for scripts, as emitted by `--error-format json`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BonsaiError::NotInRepository => "not_in_repository",
+            BonsaiError::NotInitialized => "not_initialized",
+            BonsaiError::AlreadyInitialized => "already_initialized",
+            BonsaiError::DestinationExists { .. } => "destination_exists",
+            BonsaiError::WorktreeExists { .. } => "worktree_exists",
+            BonsaiError::WorktreeNotFound { .. } => "worktree_not_found",
+            BonsaiError::TrashEntryNotFound { .. } => "trash_entry_not_found",
+            BonsaiError::BranchNotFound { .. } => "branch_not_found",
+            BonsaiError::BranchExists { .. } => "branch_exists",
+            BonsaiError::InvalidBranchName { .. } => "invalid_branch_name",
+            BonsaiError::DirtyWorktree { .. } => "dirty_worktree",
+            BonsaiError::WouldLoseWork { .. } => "would_lose_work",
+            BonsaiError::GitCommandFailed { .. } => "git_command_failed",
+            BonsaiError::Config(_) => "config",
+            BonsaiError::SyncFailed { .. } => "sync_failed",
+            BonsaiError::ExecFailed { .. } => "exec_failed",
+            BonsaiError::HookFailed(_) => "hook_failed",
+            BonsaiError::DoctorFoundProblems { .. } => "doctor_found_problems",
+            BonsaiError::Io(_) => "io",
+        }
+    }
+
+    /// Process exit code. These values are part of the CLI contract and
+    /// documented in the README; never renumber an existing variant.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            BonsaiError::Io(_) => 1,
+            BonsaiError::NotInRepository => 10,
+            BonsaiError::NotInitialized => 11,
+            BonsaiError::AlreadyInitialized => 12,
+            BonsaiError::Config(_) => 13,
+            BonsaiError::DestinationExists { .. } => 14,
+            BonsaiError::WorktreeNotFound { .. } => 20,
+            BonsaiError::WorktreeExists { .. } => 21,
+            BonsaiError::BranchNotFound { .. } => 22,
+            BonsaiError::BranchExists { .. } => 23,
+            BonsaiError::TrashEntryNotFound { .. } => 24,
+            BonsaiError::InvalidBranchName { .. } => 25,
+            BonsaiError::DirtyWorktree { .. } => 30,
+            BonsaiError::WouldLoseWork { .. } => 31,
+            BonsaiError::GitCommandFailed { .. } => match self.git_cause() {
+                None => 40,
+                Some(GitFailureCause::WorktreeLocked) => 41,
+                Some(GitFailureCause::IndexLocked) => 42,
+                Some(GitFailureCause::BranchCheckedOut) => 43,
+            },
+            BonsaiError::HookFailed(_) => 50,
+            BonsaiError::SyncFailed { .. } => 51,
+            BonsaiError::ExecFailed { .. } => 52,
+            BonsaiError::DoctorFoundProblems { .. } => 60,
+        }
+    }
+
+    pub fn git_cause(&self) -> Option<GitFailureCause> {
+        match self {
+            BonsaiError::GitCommandFailed { stderr, .. } => GitFailureCause::classify(stderr),
+            _ => None,
+        }
+    }
+
+    /// Suggested next step, printed after the error.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            BonsaiError::InvalidBranchName {
+                suggestion: Some(suggestion),
+                ..
+            } => Some(format!("try '{suggestion}'")),
+            _ => self.git_cause().map(|c| c.hint().to_string()),
+        }
+    }
+
+    /// Structured fields of the error for machine-readable output.
+    pub fn details(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        match self {
+            BonsaiError::WorktreeExists { name }
+            | BonsaiError::WorktreeNotFound { name }
+            | BonsaiError::TrashEntryNotFound { name }
+            | BonsaiError::BranchNotFound { name }
+            | BonsaiError::BranchExists { name }
+            | BonsaiError::WouldLoseWork { name } => json!({ "name": name }),
+            BonsaiError::InvalidBranchName {
+                name,
+                reason,
+                suggestion,
+            } => json!({
+                "name": name,
+                "reason": reason,
+                "suggestion": suggestion,
+            }),
+            BonsaiError::DirtyWorktree { path } | BonsaiError::DestinationExists { path } => {
+                json!({ "path": path })
+            }
+            BonsaiError::GitCommandFailed { command, stderr } => json!({
+                "command": command,
+                "stderr": stderr,
+                "cause": self.git_cause().map(|c| c.kind()),
+            }),
+            BonsaiError::ExecFailed { count, skipped } => {
+                json!({ "count": count, "skipped": skipped })
+            }
+            BonsaiError::SyncFailed { count } | BonsaiError::DoctorFoundProblems { count } => {
+                json!({ "count": count })
+            }
+            _ => json!({}),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Print an error to stderr in the requested format.
+pub fn report(err: &BonsaiError, format: ErrorFormat) {
+    match format {
+        ErrorFormat::Text => {
+            eprintln!("Error: {err}");
+            if let Some(hint) = err.hint() {
+                eprintln!("hint: {hint}");
+            }
+        }
+        ErrorFormat::Json => {
+            let value = serde_json::json!({
+                "kind": err.kind(),
+                "message": err.to_string(),
+                "details": err.details(),
+            });
+            eprintln!("{value}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_git_failure() {
+        assert_eq!(
+            GitFailureCause::classify("fatal: cannot remove a locked working tree"),
+            Some(GitFailureCause::WorktreeLocked)
+        );
+        assert_eq!(
+            GitFailureCause::classify(
+                "fatal: Unable to create '/repo/.git/index.lock': File exists."
+            ),
+            Some(GitFailureCause::IndexLocked)
+        );
+        assert_eq!(
+            GitFailureCause::classify("fatal: 'main' is already checked out at '/repo'"),
+            Some(GitFailureCause::BranchCheckedOut)
+        );
+        assert_eq!(GitFailureCause::classify("fatal: bad revision"), None);
+    }
+}
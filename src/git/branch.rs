@@ -2,6 +2,11 @@ use crate::error::Result;
 use crate::git::runner::GitRunner;
 
 impl GitRunner {
+    pub fn branch_create(&self, name: &str, start: &str) -> Result<()> {
+        self.run(&["branch", name, start])?;
+        Ok(())
+    }
+
     pub fn branch_delete(&self, name: &str, force: bool) -> Result<()> {
         let flag = if force { "-D" } else { "-d" };
         self.run(&["branch", flag, name])?;
@@ -21,13 +26,120 @@ impl GitRunner {
     }
 
     pub fn branch_is_merged(&self, branch: &str, base: &str) -> Result<bool> {
-        let output = self.run(&["branch", "--merged", base])?;
-        Ok(output
-            .lines()
-            .any(|line| line.trim().trim_start_matches("* ") == branch))
+        let output = self.run(&["branch", "--merged", base, "--format=%(refname:short)"])?;
+        Ok(output.lines().any(|line| line.trim() == branch))
+    }
+
+    /// One-line summaries of commits on `branch` that are not on `target`.
+    pub fn unpushed_commits(&self, branch: &str, target: &str) -> Result<Vec<String>> {
+        let range = format!("{target}..{branch}");
+        let output = self.run(&["log", "--format=%h %s", &range])?;
+        Ok(output.lines().map(str::to_string).collect())
     }
 
     pub fn current_branch(&self) -> Result<String> {
         self.run(&["rev-parse", "--abbrev-ref", "HEAD"])
     }
+
+    /// Record the branch a new branch was created from, so base comparisons
+    /// keep using it even if the default branch changes later.
+    pub fn set_branch_base(&self, branch: &str, base: &str) -> Result<()> {
+        let key = format!("branch.{branch}.bonsai-base");
+        self.run(&["config", &key, base])?;
+        Ok(())
+    }
+
+    pub fn branch_base(&self, branch: &str) -> Option<String> {
+        let key = format!("branch.{branch}.bonsai-base");
+        self.run(&["config", "--get", &key])
+            .ok()
+            .filter(|b| !b.is_empty())
+    }
+
+    /// Record the profile a branch's worktree was added with, so later
+    /// commands apply it even when its `match` does not cover the branch.
+    pub fn set_branch_profile(&self, branch: &str, profile: &str) -> Result<()> {
+        let key = format!("branch.{branch}.bonsai-profile");
+        self.run(&["config", &key, profile])?;
+        Ok(())
+    }
+
+    pub fn branch_profile(&self, branch: &str) -> Option<String> {
+        let key = format!("branch.{branch}.bonsai-profile");
+        self.run(&["config", "--get", &key])
+            .ok()
+            .filter(|p| !p.is_empty())
+    }
+
+    /// Record the issue a branch was started for, shown by `list` and `status`.
+    pub fn set_branch_issue(&self, branch: &str, issue: &str) -> Result<()> {
+        let key = format!("branch.{branch}.bonsai-issue");
+        self.run(&["config", &key, issue])?;
+        Ok(())
+    }
+
+    pub fn branch_issue(&self, branch: &str) -> Option<String> {
+        let key = format!("branch.{branch}.bonsai-issue");
+        self.run(&["config", "--get", &key])
+            .ok()
+            .filter(|i| !i.is_empty())
+    }
+
+    /// Base branch to compare `branch` against: the recorded base if any,
+    /// otherwise the default branch.
+    pub fn base_branch_for(&self, branch: &str, configured: Option<&str>) -> Option<String> {
+        self.branch_base(branch)
+            .or_else(|| self.default_branch(configured))
+    }
+
+    /// Name of the remote to consult for the default branch: `origin` if
+    /// present, otherwise the first configured remote.
+    pub fn default_remote(&self) -> Option<String> {
+        let output = self.run(&["remote"]).ok()?;
+        let remotes: Vec<&str> = output.lines().map(str::trim).collect();
+        if remotes.contains(&"origin") {
+            Some("origin".to_string())
+        } else {
+            remotes
+                .first()
+                .filter(|r| !r.is_empty())
+                .map(|r| r.to_string())
+        }
+    }
+
+    /// Resolve the branch used as the base for new branches and merge checks.
+    ///
+    /// Resolution order: the configured `defaults.base_branch`, the remote's
+    /// `HEAD` (`refs/remotes/<remote>/HEAD`), `init.defaultBranch`, then
+    /// well-known local branch names. A branch that only exists on the remote
+    /// is returned as `<remote>/<branch>`.
+    pub fn default_branch(&self, configured: Option<&str>) -> Option<String> {
+        if let Some(branch) = configured.filter(|b| !b.is_empty()) {
+            return Some(branch.to_string());
+        }
+
+        if let Some(remote) = self.default_remote() {
+            let head_ref = format!("refs/remotes/{remote}/HEAD");
+            if let Ok(target) = self.run(&["symbolic-ref", "--quiet", &head_ref]) {
+                let prefix = format!("refs/remotes/{remote}/");
+                if let Some(branch) = target.strip_prefix(&prefix) {
+                    if self.branch_exists(branch).unwrap_or(false) {
+                        return Some(branch.to_string());
+                    }
+                    return Some(format!("{remote}/{branch}"));
+                }
+            }
+        }
+
+        if let Ok(branch) = self.run(&["config", "--get", "init.defaultBranch"]) {
+            if !branch.is_empty() && self.branch_exists(&branch).unwrap_or(false) {
+                return Some(branch);
+            }
+        }
+
+        ["main", "master", "trunk", "develop"]
+            .iter()
+            .find(|b| self.branch_exists(b).unwrap_or(false))
+            .map(|b| b.to_string())
+    }
 }
@@ -1,8 +1,10 @@
 pub mod branch;
+pub mod refs;
 pub mod runner;
 pub mod status;
+pub mod sync;
 pub mod worktree;
 
 pub use runner::GitRunner;
-pub use status::StatusSummary;
+pub use status::{BaseComparison, Operation, StatusSummary};
 pub use worktree::{AddOptions, WorktreeInfo};
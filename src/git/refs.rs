@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::git::runner::GitRunner;
+
+impl GitRunner {
+    /// Absolute path of the git directory shared by all worktrees.
+    pub fn git_common_dir(&self) -> Result<PathBuf> {
+        let dir = self.run(&["rev-parse", "--git-common-dir"])?;
+        let dir = PathBuf::from(dir);
+        if dir.is_absolute() {
+            Ok(dir)
+        } else {
+            Ok(self.work_dir.join(dir))
+        }
+    }
+
+    pub fn rev_parse(&self, rev: &str) -> Result<String> {
+        self.run(&["rev-parse", "--verify", "--quiet", rev])
+    }
+
+    pub fn update_ref(&self, name: &str, target: &str) -> Result<()> {
+        self.run(&["update-ref", name, target])?;
+        Ok(())
+    }
+
+    pub fn delete_ref(&self, name: &str) -> Result<()> {
+        self.run(&["update-ref", "-d", name])?;
+        Ok(())
+    }
+
+    /// Stash uncommitted and untracked changes in the worktree at `path`,
+    /// leaving it clean. Returns the stash commit, or `None` if there was
+    /// nothing to save. The entry stays in the shared stash list until
+    /// [`GitRunner::drop_stash`], so the changes are never unreferenced.
+    pub fn stash_changes(&self, path: &Path, message: &str) -> Result<Option<String>> {
+        let path_str = path.to_string_lossy();
+        let before = self.rev_parse("refs/stash").ok();
+        self.run(&[
+            "-C",
+            &path_str,
+            "stash",
+            "push",
+            "--include-untracked",
+            "-m",
+            message,
+        ])?;
+        let after = self.rev_parse("refs/stash").ok();
+
+        match after {
+            Some(sha) if !sha.is_empty() && after != before => Ok(Some(sha)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Remove the stash entry for commit `sha` from the stash list.
+    pub fn drop_stash(&self, path: &Path, sha: &str) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        let list = self.run(&["-C", &path_str, "stash", "list", "--format=%H"])?;
+        if let Some(index) = list.lines().position(|line| line == sha) {
+            let entry = format!("stash@{{{index}}}");
+            self.run(&["-C", &path_str, "stash", "drop", "--quiet", &entry])?;
+        }
+        Ok(())
+    }
+
+    /// Put changes saved by `stash_changes` back and drop their stash entry.
+    pub fn unstash(&self, path: &Path, sha: &str) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        self.run(&["-C", &path_str, "stash", "apply", "--index", "--quiet", sha])?;
+        self.drop_stash(path, sha)
+    }
+
+    /// Apply a stash commit (as created by `stash_changes`) to a worktree.
+    pub fn apply_snapshot(&self, path: &Path, sha: &str) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        self.run(&["-C", &path_str, "stash", "apply", sha])?;
+        Ok(())
+    }
+}
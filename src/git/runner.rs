@@ -6,7 +6,14 @@ use colored::Colorize;
 use crate::error::{BonsaiError, Result};
 
 pub struct GitRunner {
+    /// Directory bonsai works from: `worktree_dir` and hook sources are
+    /// relative to it. The main worktree, or the directory holding a bare repo.
     pub repo_root: PathBuf,
+    /// Where `.bonsai.toml` lives.
+    pub config_path: PathBuf,
+    /// Directory git commands run in; the repository itself when bare.
+    pub work_dir: PathBuf,
+    pub bare: bool,
     pub dry_run: bool,
     pub verbose: bool,
     pub no_color: bool,
@@ -15,13 +22,54 @@ pub struct GitRunner {
 impl GitRunner {
     pub fn new(repo_root: PathBuf, dry_run: bool, verbose: bool, no_color: bool) -> Self {
         Self {
+            config_path: repo_root.join(".bonsai.toml"),
+            work_dir: repo_root.clone(),
             repo_root,
+            bare: false,
             dry_run,
             verbose,
             no_color,
         }
     }
 
+    /// Locate the repository containing `from`, including bare repositories
+    /// and worktrees linked to them.
+    pub fn discover(from: &Path, dry_run: bool, verbose: bool, no_color: bool) -> Result<Self> {
+        let common_dir = rev_parse_in(from, &["--path-format=absolute", "--git-common-dir"])?;
+        let common_dir = PathBuf::from(common_dir);
+
+        let bare = Command::new("git")
+            .args(["config", "--bool", "core.bare"])
+            .current_dir(from)
+            .output()
+            .is_ok_and(|o| String::from_utf8_lossy(&o.stdout).trim() == "true");
+
+        if !bare {
+            // The main worktree owns the common dir, so linked worktrees
+            // resolve to the same root. A separated git dir has no such
+            // parent; fall back to the worktree we are in.
+            let repo_root = match common_dir.file_name() {
+                Some(name) if name == ".git" => common_dir
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(|| common_dir.clone()),
+                _ => Self::find_repo_root(from)?,
+            };
+            return Ok(Self::new(repo_root, dry_run, verbose, no_color));
+        }
+
+        let layout = BareLayout::of(&common_dir);
+        Ok(Self {
+            repo_root: layout.root,
+            config_path: layout.config_path,
+            work_dir: common_dir,
+            bare: true,
+            dry_run,
+            verbose,
+            no_color,
+        })
+    }
+
     /// Find the git repository root from the given path.
     pub fn find_repo_root(from: &Path) -> Result<PathBuf> {
         let output = Command::new("git")
@@ -64,7 +112,7 @@ impl GitRunner {
 
         let output = Command::new("git")
             .args(args)
-            .current_dir(&self.repo_root)
+            .current_dir(&self.work_dir)
             .output()
             .map_err(|e| BonsaiError::GitCommandFailed {
                 command: cmd_str.clone(),
@@ -82,3 +130,69 @@ impl GitRunner {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 }
+
+fn rev_parse_in(from: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("rev-parse")
+        .args(args)
+        .current_dir(from)
+        .output()
+        .map_err(|e| BonsaiError::GitCommandFailed {
+            command: format!("git rev-parse {}", args.join(" ")),
+            stderr: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(BonsaiError::NotInRepository);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Where bonsai keeps its files for a bare repository.
+///
+/// A bare repo in a directory of its own (`project/.bare` next to a
+/// `project/.git` file, as set up by `bonsai clone --bare`) keeps
+/// `.bonsai.toml` and worktrees beside it. A bare repo among others
+/// (`~/src/project.git`) keeps its config inside the repository and its
+/// worktrees in `~/src/project/`.
+pub struct BareLayout {
+    pub root: PathBuf,
+    pub config_path: PathBuf,
+    /// `worktree_dir` written by `init`.
+    pub worktree_dir: String,
+}
+
+impl BareLayout {
+    pub fn of(common_dir: &Path) -> Self {
+        let root = common_dir
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| common_dir.to_path_buf());
+        let name = common_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let dedicated = name == ".bare" || root.join(".git").is_file();
+        if dedicated {
+            return Self {
+                config_path: root.join(".bonsai.toml"),
+                root,
+                worktree_dir: ".".to_string(),
+            };
+        }
+
+        let sibling = root.join(".bonsai.toml");
+        let config_path = if sibling.exists() {
+            sibling
+        } else {
+            common_dir.join("bonsai.toml")
+        };
+        let stem = name.strip_suffix(".git").unwrap_or(&name);
+        Self {
+            root,
+            config_path,
+            worktree_dir: format!("{stem}.worktrees"),
+        }
+    }
+}
@@ -2,28 +2,91 @@ use std::path::Path;
 
 use crate::error::Result;
 use crate::git::runner::GitRunner;
+use crate::git::worktree::WorktreeInfo;
 
-#[derive(Debug, Clone)]
+/// A multi-step git operation left in progress in a worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Rebase,
+    Am,
+    Merge,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl Operation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Operation::Rebase => "rebase",
+            Operation::Am => "am",
+            Operation::Merge => "merge",
+            Operation::CherryPick => "cherry-pick",
+            Operation::Revert => "revert",
+            Operation::Bisect => "bisect",
+        }
+    }
+
+    /// Detect an in-progress operation from the worktree's own git dir.
+    pub fn detect(git_dir: &Path) -> Option<Self> {
+        if git_dir.join("rebase-merge").is_dir() {
+            Some(Operation::Rebase)
+        } else if git_dir.join("rebase-apply").is_dir() {
+            if git_dir.join("rebase-apply/applying").exists() {
+                Some(Operation::Am)
+            } else {
+                Some(Operation::Rebase)
+            }
+        } else if git_dir.join("MERGE_HEAD").exists() {
+            Some(Operation::Merge)
+        } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+            Some(Operation::CherryPick)
+        } else if git_dir.join("REVERT_HEAD").exists() {
+            Some(Operation::Revert)
+        } else if git_dir.join("BISECT_LOG").exists() {
+            Some(Operation::Bisect)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct StatusSummary {
     pub modified: usize,
     pub added: usize,
     pub deleted: usize,
     pub untracked: usize,
+    pub conflicted: usize,
+    /// Entries with changes in the index.
+    pub staged: usize,
+    /// Entries with changes in the working tree.
+    pub unstaged: usize,
+    pub submodules: usize,
+    pub stashes: usize,
+    pub operation: Option<Operation>,
     pub ahead: usize,
     pub behind: usize,
 }
 
 impl StatusSummary {
     pub fn is_clean(&self) -> bool {
-        self.modified == 0 && self.added == 0 && self.deleted == 0 && self.untracked == 0
+        self.modified == 0
+            && self.added == 0
+            && self.deleted == 0
+            && self.untracked == 0
+            && self.conflicted == 0
+            && self.submodules == 0
     }
 
     pub fn short_display(&self) -> String {
-        if self.is_clean() && self.ahead == 0 && self.behind == 0 {
-            return "clean".to_string();
-        }
-
         let mut parts = Vec::new();
+        if let Some(op) = self.operation {
+            parts.push(op.label().to_uppercase());
+        }
+        if self.conflicted > 0 {
+            parts.push(format!("{}U", self.conflicted));
+        }
         if self.modified > 0 {
             parts.push(format!("{}M", self.modified));
         }
@@ -36,6 +99,12 @@ impl StatusSummary {
         if self.untracked > 0 {
             parts.push(format!("{}?", self.untracked));
         }
+        if self.submodules > 0 {
+            parts.push(format!("{}S", self.submodules));
+        }
+        if self.stashes > 0 {
+            parts.push(format!("{}$", self.stashes));
+        }
         if self.ahead > 0 {
             parts.push(format!("⇡{}", self.ahead));
         }
@@ -51,55 +120,301 @@ impl StatusSummary {
     }
 }
 
+/// Divergence of a worktree's HEAD from its base branch.
+#[derive(Debug, Clone)]
+pub struct BaseComparison {
+    pub base: String,
+    pub ahead: usize,
+    pub behind: usize,
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl BaseComparison {
+    /// The base has gained commits since the worktree branched off it.
+    pub fn base_moved(&self) -> bool {
+        self.behind > 0
+    }
+
+    pub fn short_display(&self) -> String {
+        let mut parts = vec![self.base.clone()];
+        if self.ahead == 0 && self.behind == 0 {
+            parts.push("=".to_string());
+        }
+        if self.ahead > 0 {
+            parts.push(format!("⇡{}", self.ahead));
+        }
+        if self.behind > 0 {
+            parts.push(format!("⇣{}", self.behind));
+        }
+        if self.files_changed > 0 {
+            parts.push(format!(
+                "{}f +{} -{}",
+                self.files_changed, self.insertions, self.deletions
+            ));
+        }
+        parts.join(" ")
+    }
+}
+
 impl GitRunner {
     pub fn status_summary(&self, path: &Path) -> Result<StatusSummary> {
         let path_str = path.to_string_lossy();
         let output = self.run(&["-C", &path_str, "status", "--porcelain=v2", "--branch"])?;
 
-        let mut summary = StatusSummary {
-            modified: 0,
-            added: 0,
-            deleted: 0,
-            untracked: 0,
-            ahead: 0,
-            behind: 0,
-        };
-
-        for line in output.lines() {
-            if let Some(rest) = line.strip_prefix("# branch.ab ") {
-                for part in rest.split_whitespace() {
-                    if let Some(n) = part.strip_prefix('+') {
-                        summary.ahead = n.parse().unwrap_or(0);
-                    } else if let Some(n) = part.strip_prefix('-') {
-                        summary.behind = n.parse().unwrap_or(0);
-                    }
-                }
-            } else if line.starts_with("1 ") || line.starts_with("2 ") {
-                let xy: &str = line.split_whitespace().nth(1).unwrap_or("");
-                let x = xy.chars().next().unwrap_or('.');
-                let y = xy.chars().nth(1).unwrap_or('.');
-
-                match x {
-                    'A' => summary.added += 1,
-                    'D' => summary.deleted += 1,
-                    'M' | 'R' | 'C' => summary.modified += 1,
-                    _ => {}
-                }
-                match y {
-                    'M' => summary.modified += 1,
-                    'D' => summary.deleted += 1,
-                    _ => {}
-                }
-            } else if line.starts_with("? ") {
-                summary.untracked += 1;
-            }
+        let mut summary = parse_status_porcelain(&output);
+
+        let git_dir = self.run(&["-C", &path_str, "rev-parse", "--absolute-git-dir"])?;
+        if !git_dir.is_empty() {
+            summary.operation = Operation::detect(Path::new(&git_dir));
+        }
+
+        if let Some(branch) = branch_head(&output) {
+            summary.stashes = self.stash_count_for(path, branch)?;
         }
 
         Ok(summary)
     }
 
+    /// Count stashes created while `branch` was checked out. The stash list is
+    /// shared by all worktrees, so entries are matched on their message. Run
+    /// in the worktree at `path`, as `git stash` needs a work tree.
+    pub fn stash_count_for(&self, path: &Path, branch: &str) -> Result<usize> {
+        let path_str = path.to_string_lossy();
+        let output = self.run(&["-C", &path_str, "stash", "list", "--format=%gs"])?;
+        Ok(output
+            .lines()
+            .filter(|line| stash_branch(line) == Some(branch))
+            .count())
+    }
+
+    /// Whether `path` (relative to the repo root) is ignored by any ignore source.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        self.run(&["check-ignore", "-q", path]).is_ok()
+    }
+
+    /// Tracked files with staged or unstaged changes.
+    pub fn uncommitted_files(&self, path: &Path) -> Result<Vec<String>> {
+        let path_str = path.to_string_lossy();
+        let output = self.run(&["-C", &path_str, "diff", "HEAD", "--name-only"])?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    pub fn untracked_files(&self, path: &Path) -> Result<Vec<String>> {
+        let path_str = path.to_string_lossy();
+        let output = self.run(&[
+            "-C",
+            &path_str,
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+        ])?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    /// Ignored files, with wholly ignored directories collapsed to `dir/`.
+    pub fn ignored_files(&self, path: &Path) -> Result<Vec<String>> {
+        let path_str = path.to_string_lossy();
+        let output = self.run(&[
+            "-C",
+            &path_str,
+            "ls-files",
+            "--others",
+            "--ignored",
+            "--exclude-standard",
+            "--directory",
+        ])?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    /// Compare the worktree's HEAD against `base`: commits on either side and
+    /// the diffstat from the merge base.
+    pub fn compare_to_base(&self, path: &Path, base: &str) -> Result<BaseComparison> {
+        let path_str = path.to_string_lossy();
+        let range = format!("{base}...HEAD");
+
+        let counts = self.run(&[
+            "-C",
+            &path_str,
+            "rev-list",
+            "--left-right",
+            "--count",
+            &range,
+        ])?;
+        let mut counts = counts.split_whitespace().map(|n| n.parse().unwrap_or(0));
+        let behind = counts.next().unwrap_or(0);
+        let ahead = counts.next().unwrap_or(0);
+
+        let shortstat = self.run(&["-C", &path_str, "diff", "--shortstat", &range])?;
+        let (files_changed, insertions, deletions) = parse_shortstat(&shortstat);
+
+        Ok(BaseComparison {
+            base: base.to_string(),
+            ahead,
+            behind,
+            files_changed,
+            insertions,
+            deletions,
+        })
+    }
+
+    /// Compare a linked worktree against its recorded or default base branch.
+    /// Returns `None` for the main worktree, detached heads and the base itself.
+    pub fn worktree_base_comparison(
+        &self,
+        wt: &WorktreeInfo,
+        configured: Option<&str>,
+    ) -> Option<BaseComparison> {
+        if wt.is_main {
+            return None;
+        }
+        let branch = wt.branch.as_deref()?;
+        let base = self.base_branch_for(branch, configured)?;
+        if base == branch {
+            return None;
+        }
+        self.compare_to_base(&wt.path, &base).ok()
+    }
+
     pub fn last_commit_date(&self, path: &Path) -> Result<String> {
         let path_str = path.to_string_lossy();
         self.run(&["-C", &path_str, "log", "-1", "--format=%cr"])
     }
 }
+
+fn parse_status_porcelain(output: &str) -> StatusSummary {
+    let mut summary = StatusSummary::default();
+
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("# branch.ab ") {
+            for part in rest.split_whitespace() {
+                if let Some(n) = part.strip_prefix('+') {
+                    summary.ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = part.strip_prefix('-') {
+                    summary.behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if line.starts_with("1 ") || line.starts_with("2 ") {
+            let mut fields = line.split_whitespace().skip(1);
+            let xy = fields.next().unwrap_or("");
+            let sub = fields.next().unwrap_or("N...");
+            let x = xy.chars().next().unwrap_or('.');
+            let y = xy.chars().nth(1).unwrap_or('.');
+
+            if x != '.' {
+                summary.staged += 1;
+            }
+            if y != '.' {
+                summary.unstaged += 1;
+            }
+            if sub.starts_with('S') {
+                summary.submodules += 1;
+                continue;
+            }
+
+            match x {
+                'A' => summary.added += 1,
+                'D' => summary.deleted += 1,
+                'M' | 'R' | 'C' => summary.modified += 1,
+                _ => {}
+            }
+            match y {
+                'M' => summary.modified += 1,
+                'D' => summary.deleted += 1,
+                _ => {}
+            }
+        } else if line.starts_with("u ") {
+            summary.conflicted += 1;
+        } else if line.starts_with("? ") {
+            summary.untracked += 1;
+        }
+    }
+
+    summary
+}
+
+/// Branch name from the `# branch.head` header, if not detached.
+fn branch_head(output: &str) -> Option<&str> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("# branch.head "))
+        .filter(|b| *b != "(detached)")
+}
+
+/// Branch a stash entry was created on, from its reflog subject
+/// (`WIP on <branch>: ...` or `On <branch>: ...`).
+fn stash_branch(subject: &str) -> Option<&str> {
+    let rest = subject
+        .strip_prefix("WIP on ")
+        .or_else(|| subject.strip_prefix("On "))?;
+    rest.split_once(':').map(|(branch, _)| branch)
+}
+
+/// Parse `git diff --shortstat` output, e.g.
+/// ` 3 files changed, 10 insertions(+), 2 deletions(-)`.
+fn parse_shortstat(output: &str) -> (usize, usize, usize) {
+    let mut files = 0;
+    let mut insertions = 0;
+    let mut deletions = 0;
+
+    for part in output.split(',') {
+        let mut words = part.split_whitespace();
+        let n = words.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+        match words.next() {
+            Some(w) if w.starts_with("file") => files = n,
+            Some(w) if w.starts_with("insertion") => insertions = n,
+            Some(w) if w.starts_with("deletion") => deletions = n,
+            _ => {}
+        }
+    }
+
+    (files, insertions, deletions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_porcelain() {
+        let input = "\
+# branch.oid 1234567890123456789012345678901234567890
+# branch.head feature/x
+# branch.ab +2 -1
+1 M. N... 100644 100644 100644 abc abc staged.rs
+1 .M N... 100644 100644 100644 abc abc unstaged.rs
+1 .M SC.. 160000 160000 160000 abc abc vendor/lib
+u UU N... 100644 100644 100644 100644 abc abc abc conflict.rs
+? new.rs
+";
+        let summary = parse_status_porcelain(input);
+        assert_eq!(summary.modified, 2);
+        assert_eq!(summary.staged, 1);
+        assert_eq!(summary.unstaged, 2);
+        assert_eq!(summary.submodules, 1);
+        assert_eq!(summary.conflicted, 1);
+        assert_eq!(summary.untracked, 1);
+        assert_eq!(summary.ahead, 2);
+        assert_eq!(summary.behind, 1);
+        assert_eq!(branch_head(input), Some("feature/x"));
+        assert_eq!(summary.short_display(), "1U 2M 1? 1S ⇡2 ⇣1");
+    }
+
+    #[test]
+    fn test_stash_branch() {
+        assert_eq!(stash_branch("WIP on main: abc123 initial"), Some("main"));
+        assert_eq!(stash_branch("On feature/x: my stash"), Some("feature/x"));
+        assert_eq!(stash_branch("autostash"), None);
+    }
+
+    #[test]
+    fn test_parse_shortstat() {
+        assert_eq!(
+            parse_shortstat(" 3 files changed, 10 insertions(+), 2 deletions(-)"),
+            (3, 10, 2)
+        );
+        assert_eq!(parse_shortstat(" 1 file changed, 1 deletion(-)"), (1, 0, 1));
+        assert_eq!(parse_shortstat(""), (0, 0, 0));
+    }
+}
@@ -0,0 +1,54 @@
+use std::path::Path;
+
+use crate::error::Result;
+use crate::git::runner::GitRunner;
+
+impl GitRunner {
+    /// Fetch every remote once. Does nothing when no remote is configured.
+    pub fn fetch_all(&self) -> Result<()> {
+        if self.default_remote().is_none() {
+            return Ok(());
+        }
+        self.run(&["fetch", "--all", "--prune"])?;
+        Ok(())
+    }
+
+    /// Upstream of the branch checked out at `path`, e.g. `origin/main`.
+    pub fn upstream(&self, path: &Path) -> Option<String> {
+        let path_str = path.to_string_lossy();
+        self.run(&[
+            "-C",
+            &path_str,
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ])
+        .ok()
+        .filter(|u| !u.is_empty())
+    }
+
+    /// Number of commits reachable from `range` (e.g. `HEAD..origin/main`).
+    pub fn rev_count(&self, path: &Path, range: &str) -> Result<usize> {
+        let path_str = path.to_string_lossy();
+        let output = self.run(&["-C", &path_str, "rev-list", "--count", range])?;
+        Ok(output.parse().unwrap_or(0))
+    }
+
+    pub fn merge_ff_only(&self, path: &Path, target: &str) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        self.run(&["-C", &path_str, "merge", "--ff-only", target])?;
+        Ok(())
+    }
+
+    /// Rebase the worktree at `path` onto `target`. On failure the rebase is
+    /// aborted so the worktree is never left half-applied.
+    pub fn rebase_or_abort(&self, path: &Path, target: &str) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        if let Err(e) = self.run(&["-C", &path_str, "rebase", target]) {
+            let _ = self.run(&["-C", &path_str, "rebase", "--abort"]);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
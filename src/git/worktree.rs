@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::Result;
 use crate::git::runner::GitRunner;
@@ -63,9 +63,14 @@ impl GitRunner {
         Ok(())
     }
 
+    /// Worktrees with checkouts. The entry git lists for a bare repository
+    /// itself is left out.
     pub fn worktree_list(&self) -> Result<Vec<WorktreeInfo>> {
         let output = self.run(&["worktree", "list", "--porcelain"])?;
-        Ok(parse_worktree_porcelain(&output))
+        Ok(parse_worktree_porcelain(&output)
+            .into_iter()
+            .filter(|w| !w.is_bare)
+            .collect())
     }
 
     pub fn worktree_move(
@@ -84,6 +89,18 @@ impl GitRunner {
         Ok(())
     }
 
+    /// Repair administrative files, e.g. after worktrees were moved by hand.
+    pub fn worktree_repair(&self, paths: &[&std::path::Path]) -> Result<()> {
+        let paths: Vec<String> = paths
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        let mut args = vec!["worktree", "repair"];
+        args.extend(paths.iter().map(String::as_str));
+        self.run(&args)?;
+        Ok(())
+    }
+
     pub fn worktree_lock(&self, worktree: &std::path::Path, reason: Option<&str>) -> Result<()> {
         let path_str = worktree.to_string_lossy();
         let mut args = vec!["worktree", "lock"];
@@ -103,6 +120,15 @@ impl GitRunner {
     }
 }
 
+/// Worktree path recorded in git's admin dir for the worktree at `dir`,
+/// following its `.git` file (`gitdir: <admin dir>`).
+pub fn registered_path(dir: &Path) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(dir.join(".git")).ok()?;
+    let admin_dir = content.trim().strip_prefix("gitdir: ")?;
+    let gitdir = std::fs::read_to_string(Path::new(admin_dir).join("gitdir")).ok()?;
+    Path::new(gitdir.trim()).parent().map(Path::to_path_buf)
+}
+
 fn parse_worktree_porcelain(output: &str) -> Vec<WorktreeInfo> {
     let mut worktrees = Vec::new();
     let mut is_first = true;
@@ -135,12 +161,13 @@ fn parse_worktree_porcelain(output: &str) -> Vec<WorktreeInfo> {
             } else if let Some(reason) = line.strip_prefix("locked ") {
                 is_locked = true;
                 lock_reason = Some(reason.to_string());
-            } else if line == "prunable" {
+            } else if line == "prunable" || line.starts_with("prunable ") {
                 is_prunable = true;
             }
         }
 
-        let is_main = is_first;
+        // A bare repository has no main worktree
+        let is_main = is_first && !is_bare;
         is_first = false;
 
         worktrees.push(WorktreeInfo {
@@ -207,4 +234,37 @@ locked reason for locking
         assert!(result[1].is_locked);
         assert_eq!(result[1].lock_reason.as_deref(), Some("reason for locking"));
     }
+
+    #[test]
+    fn test_parse_worktree_prunable() {
+        let input = "\
+worktree /home/user/project
+HEAD abc1234567890123456789012345678901234abcd
+branch refs/heads/main
+
+worktree /home/user/project/.bonsai/gone
+HEAD def4567890123456789012345678901234567890
+branch refs/heads/gone
+prunable gitdir file points to non-existent location
+";
+        let result = parse_worktree_porcelain(input);
+        assert!(!result[0].is_prunable);
+        assert!(result[1].is_prunable);
+    }
+
+    #[test]
+    fn test_parse_worktree_bare() {
+        let input = "\
+worktree /home/user/project.git
+bare
+
+worktree /home/user/project.worktrees/main
+HEAD abc1234567890123456789012345678901234abcd
+branch refs/heads/main
+";
+        let result = parse_worktree_porcelain(input);
+        assert!(result[0].is_bare);
+        assert!(!result[0].is_main);
+        assert!(!result[1].is_main);
+    }
 }
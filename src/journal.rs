@@ -0,0 +1,356 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+use crate::git::worktree::AddOptions;
+use crate::trash;
+
+/// A single git operation performed by a mutating command, with enough
+/// information to invert it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Step {
+    #[serde(rename = "branch_rename")]
+    BranchRename { from: String, to: String },
+    #[serde(rename = "worktree_move")]
+    WorktreeMove { from: PathBuf, to: PathBuf },
+    #[serde(rename = "worktree_remove")]
+    WorktreeRemove {
+        path: PathBuf,
+        branch: Option<String>,
+        head: String,
+        /// Trash snapshot holding the worktree's uncommitted changes.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trash_id: Option<String>,
+    },
+    #[serde(rename = "branch_delete")]
+    BranchDelete { name: String, head: String },
+}
+
+impl Step {
+    /// Revert this step.
+    pub fn undo(&self, git: &GitRunner) -> Result<()> {
+        match self {
+            Step::BranchRename { from, to } => git.branch_rename(to, from),
+            Step::WorktreeMove { from, to } => git.worktree_move(to, from),
+            Step::BranchDelete { name, head } => git.branch_create(name, head),
+            Step::WorktreeRemove {
+                path,
+                branch,
+                head,
+                trash_id,
+            } => {
+                if path.exists() {
+                    return Err(BonsaiError::WorktreeExists {
+                        name: path.display().to_string(),
+                    });
+                }
+                match branch {
+                    Some(branch) => {
+                        if !git.branch_exists(branch)? {
+                            git.branch_create(branch, head)?;
+                        }
+                        let opts = AddOptions {
+                            create_branch: false,
+                            base: None,
+                            detach: false,
+                        };
+                        git.worktree_add(path, branch, &opts)?;
+                    }
+                    None => {
+                        let opts = AddOptions {
+                            create_branch: false,
+                            base: None,
+                            detach: true,
+                        };
+                        git.worktree_add(path, head, &opts)?;
+                    }
+                }
+                if let Some(id) = trash_id {
+                    if let Ok(entry) = trash::find(git, id) {
+                        if let Some(ref changes) = entry.changes {
+                            git.apply_snapshot(path, changes)?;
+                        }
+                        trash::discard(git, &entry)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Shell command(s) that revert this step, for manual recovery.
+    pub fn undo_command(&self) -> String {
+        match self {
+            Step::BranchRename { from, to } => format!("git branch -m {to} {from}"),
+            Step::WorktreeMove { from, to } => {
+                format!("git worktree move {} {}", to.display(), from.display())
+            }
+            Step::BranchDelete { name, head } => format!("git branch {name} {head}"),
+            Step::WorktreeRemove {
+                path, branch, head, ..
+            } => match branch {
+                Some(branch) => format!("git worktree add {} {branch}", path.display()),
+                None => format!("git worktree add --detach {} {head}", path.display()),
+            },
+        }
+    }
+
+    /// How the repository no longer matches what this step left behind, if
+    /// it does not. Undoing such a step would act on someone else's work.
+    pub fn drift(&self, git: &GitRunner) -> Result<Option<String>> {
+        let drift = match self {
+            Step::BranchRename { from, to } => {
+                if !git.branch_exists(to)? {
+                    Some(format!("branch {to} no longer exists"))
+                } else if git.branch_exists(from)? {
+                    Some(format!("branch {from} exists again"))
+                } else {
+                    None
+                }
+            }
+            Step::WorktreeMove { from, to } => {
+                if !to.exists() {
+                    Some(format!("{} no longer exists", to.display()))
+                } else if from.exists() {
+                    Some(format!("{} exists again", from.display()))
+                } else {
+                    None
+                }
+            }
+            Step::WorktreeRemove { path, .. } => path
+                .exists()
+                .then(|| format!("{} exists again", path.display())),
+            Step::BranchDelete { name, .. } => git
+                .branch_exists(name)?
+                .then(|| format!("branch {name} exists again")),
+        };
+        Ok(drift)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Step::BranchRename { from, to } => format!("rename branch {from} -> {to}"),
+            Step::WorktreeMove { from, to } => {
+                format!("move worktree {} -> {}", from.display(), to.display())
+            }
+            Step::BranchDelete { name, .. } => format!("delete branch {name}"),
+            Step::WorktreeRemove { path, .. } => format!("remove worktree {}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub command: String,
+    pub started_at: u64,
+    #[serde(default)]
+    pub steps: Vec<Step>,
+}
+
+/// Journal of the operation in progress. Every completed step is written to
+/// disk immediately so an interrupted command can still be undone.
+pub struct Journal<'a> {
+    git: &'a GitRunner,
+    path: Option<PathBuf>,
+    record: JournalRecord,
+}
+
+/// Completed operations kept for `bonsai undo`; older ones are deleted.
+const KEEP_JOURNALS: usize = 20;
+
+fn journal_dir(git: &GitRunner) -> Result<PathBuf> {
+    Ok(git.git_common_dir()?.join("bonsai").join("journal"))
+}
+
+/// Journal files in `dir`, oldest first.
+fn journal_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for file in std::fs::read_dir(dir)? {
+        let path = file?.path();
+        let Some(ts) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        files.push((ts, path));
+    }
+    files.sort();
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+impl<'a> Journal<'a> {
+    pub fn begin(git: &'a GitRunner, command: &str) -> Result<Self> {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+
+        // Nothing is executed in dry-run mode, so there is nothing to journal
+        let path = if git.dry_run {
+            None
+        } else {
+            Some(journal_dir(git)?.join(format!("{started_at}.toml")))
+        };
+
+        Ok(Self {
+            git,
+            path,
+            record: JournalRecord {
+                command: command.to_string(),
+                started_at,
+                steps: Vec::new(),
+            },
+        })
+    }
+
+    /// Run `op` and record `step` if it succeeds. On failure, every step
+    /// recorded so far is rolled back and the original error is returned.
+    pub fn step(
+        &mut self,
+        step: Step,
+        op: impl FnOnce() -> Result<()>,
+        no_color: bool,
+    ) -> Result<()> {
+        match op() {
+            Ok(()) => self.record(step),
+            Err(e) => Err(self.rollback(e, no_color)),
+        }
+    }
+
+    pub fn record(&mut self, step: Step) -> Result<()> {
+        self.record.steps.push(step);
+        self.save()
+    }
+
+    /// Mark the operation complete. It stays on disk for `bonsai undo`,
+    /// along with the most recent ones before it.
+    pub fn finish(self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if self.record.steps.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        let files = journal_files(&journal_dir(self.git)?)?;
+        let excess = files.len().saturating_sub(KEEP_JOURNALS);
+        for old in &files[..excess] {
+            std::fs::remove_file(old)?;
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        save_record(path, &self.record)
+    }
+
+    fn rollback(&mut self, err: BonsaiError, no_color: bool) -> BonsaiError {
+        if self.record.steps.is_empty() {
+            return err;
+        }
+
+        let total = self.record.steps.len();
+        if no_color {
+            eprintln!(
+                "{} failed; rolling back {total} step(s)",
+                self.record.command
+            );
+        } else {
+            eprintln!(
+                "{} failed; rolling back {total} step(s)",
+                self.record.command.yellow()
+            );
+        }
+
+        match undo_steps(self.git, &mut self.record.steps) {
+            Ok(()) => {
+                if let Some(ref path) = self.path {
+                    let _ = std::fs::remove_file(path);
+                }
+            }
+            Err(_) => {
+                let _ = self.save();
+                print_recovery(&self.record.steps, no_color);
+            }
+        }
+
+        err
+    }
+}
+
+fn save_record(path: &Path, record: &JournalRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string_pretty(record)
+        .map_err(|e| BonsaiError::Config(format!("failed to serialize journal: {e}")))?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Undo steps newest first, removing each from `steps` once reverted. Stops
+/// at the first failure, leaving the remaining steps in place.
+fn undo_steps(git: &GitRunner, steps: &mut Vec<Step>) -> Result<()> {
+    while let Some(step) = steps.last() {
+        step.undo(git)?;
+        steps.pop();
+    }
+    Ok(())
+}
+
+fn print_recovery(steps: &[Step], no_color: bool) {
+    if no_color {
+        eprintln!("Rollback incomplete. To recover, run:");
+    } else {
+        eprintln!("{}", "Rollback incomplete. To recover, run:".red());
+    }
+    for step in steps.iter().rev() {
+        eprintln!("  {}", step.undo_command());
+    }
+}
+
+/// Most recent journal entry and its file, if any.
+pub fn latest(git: &GitRunner) -> Result<Option<(PathBuf, JournalRecord)>> {
+    let Some(path) = journal_files(&journal_dir(git)?)?.pop() else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(&path)?;
+    let record: JournalRecord = toml::from_str(&content)
+        .map_err(|e| BonsaiError::Config(format!("failed to parse {}: {e}", path.display())))?;
+    Ok(Some((path, record)))
+}
+
+/// Revert the operation recorded at `path`. On failure the journal keeps the
+/// steps that could not be reverted and recovery commands are printed.
+pub fn undo(git: &GitRunner, path: &Path, mut record: JournalRecord, no_color: bool) -> Result<()> {
+    match undo_steps(git, &mut record.steps) {
+        Ok(()) => {
+            if !git.dry_run {
+                std::fs::remove_file(path)?;
+            }
+            Ok(())
+        }
+        Err(e) => {
+            if !git.dry_run {
+                save_record(path, &record)?;
+            }
+            print_recovery(&record.steps, no_color);
+            Err(e)
+        }
+    }
+}
@@ -1,14 +1,25 @@
 pub mod app;
 pub mod cli;
 pub mod commands;
+pub mod complete;
 pub mod config;
+pub mod detect;
+pub mod directive;
 pub mod error;
 pub mod git;
 pub mod hooks;
+pub mod journal;
+pub mod naming;
+pub mod pattern;
+pub mod safety;
+pub mod trash;
 
 use clap::Parser;
 
-pub fn run() -> error::Result<()> {
+/// Run the CLI and return the process exit code.
+pub fn run() -> i32 {
+    complete::handle_env();
+
     let cli = cli::Cli::parse();
 
     let no_color = cli.no_color || std::env::var("NO_COLOR").is_ok();
@@ -17,5 +28,11 @@ pub fn run() -> error::Result<()> {
         colored::control::set_override(false);
     }
 
-    app::dispatch(cli.command, cli.dry_run, cli.verbose, no_color)
+    match app::dispatch(cli.command, cli.dry_run, cli.verbose, no_color) {
+        Ok(()) => 0,
+        Err(e) => {
+            error::report(&e, cli.error_format);
+            e.exit_code()
+        }
+    }
 }
@@ -0,0 +1,263 @@
+use regex::Regex;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::BranchPolicy;
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+
+/// Check a new branch name against git's ref rules and the configured
+/// policy, before anything is created for it.
+pub fn check(git: &GitRunner, policy: &BranchPolicy, name: &str) -> Result<()> {
+    let Some(reason) = problem(git, policy, name) else {
+        return Ok(());
+    };
+    let suggestion = Some(suggest(policy, name))
+        .filter(|s| !s.is_empty() && s != name && problem(git, policy, s).is_none());
+    Err(BonsaiError::InvalidBranchName {
+        name: name.to_string(),
+        reason,
+        suggestion,
+    })
+}
+
+fn problem(git: &GitRunner, policy: &BranchPolicy, name: &str) -> Option<String> {
+    // `--branch` also rejects names such as `HEAD` and ones starting with `-`
+    if name.starts_with('-') || git.run(&["check-ref-format", "--branch", name]).is_err() {
+        return Some("git does not allow it as a branch name".to_string());
+    }
+    if policy.lowercase && name.chars().any(char::is_uppercase) {
+        return Some("must be lowercase".to_string());
+    }
+    if !policy.prefixes.is_empty() && !policy.prefixes.iter().any(|p| name.starts_with(p)) {
+        return Some(format!(
+            "must start with one of: {}",
+            policy.prefixes.join(", ")
+        ));
+    }
+    if let Some(max) = policy.max_length {
+        let length = name.chars().count();
+        if length > max {
+            return Some(format!("is {length} characters long; the limit is {max}"));
+        }
+    }
+    if let Some(ref pattern) = policy.pattern {
+        if Regex::new(pattern).is_ok_and(|re| !re.is_match(name)) {
+            return Some(format!("does not match `{pattern}`"));
+        }
+    }
+    None
+}
+
+/// A name close to `name` that git accepts and that fits the policy where a
+/// mechanical fix exists. The caller checks the result before offering it.
+fn suggest(policy: &BranchPolicy, name: &str) -> String {
+    let name = if policy.lowercase {
+        name.to_lowercase()
+    } else {
+        name.to_string()
+    };
+
+    // Characters git rejects become `-`, then runs of separators collapse
+    let mut cleaned = String::new();
+    for c in name.trim().chars() {
+        let c = match c {
+            c if c.is_whitespace() || c.is_control() => '-',
+            '~' | '^' | ':' | '?' | '*' | '[' | '\\' | '@' => '-',
+            c => c,
+        };
+        let last = cleaned.chars().last();
+        if (c == '-' && last == Some('-')) || (c == '/' && last == Some('/')) {
+            continue;
+        }
+        if c == '.' && last == Some('.') {
+            continue;
+        }
+        cleaned.push(c);
+    }
+    let mut name = cleaned
+        .split('/')
+        .map(|part| {
+            part.trim_start_matches('.')
+                .trim_end_matches(".lock")
+                .trim_matches('-')
+        })
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    // `feature-login` for a `feature/` prefix
+    if !policy.prefixes.iter().any(|p| name.starts_with(p)) {
+        let fixed = policy.prefixes.iter().find_map(|prefix| {
+            let stem = prefix.strip_suffix('/')?;
+            let rest = name.strip_prefix(stem)?;
+            let rest = rest.strip_prefix('-').or_else(|| rest.strip_prefix('_'))?;
+            Some(format!("{prefix}{rest}"))
+        });
+        if let Some(fixed) = fixed {
+            name = fixed;
+        }
+    }
+
+    if let Some(max) = policy.max_length {
+        if name.chars().count() > max {
+            name = name.chars().take(max).collect();
+            name = name.trim_end_matches(['-', '/', '.']).to_string();
+        }
+    }
+    name
+}
+
+/// Longest slug taken from a title, so generated names stay readable even
+/// without a `max_length`.
+const SLUG_LENGTH: usize = 48;
+
+/// Fill in the branch template from an issue key and title, shortening the
+/// slug at a word boundary to fit the slug and `max_length` limits.
+pub fn generate(
+    policy: &BranchPolicy,
+    kind: Option<&str>,
+    issue: Option<&str>,
+    title: Option<&str>,
+) -> Result<String> {
+    let kind = kind.unwrap_or(&policy.default_type);
+    let issue = issue.map(str::trim).unwrap_or_default();
+    let title = title.unwrap_or_default();
+    let mut slug = shorten(&slugify(title), SLUG_LENGTH);
+    // `{type}` alone would name a branch `feature`
+    if issue.is_empty() && slug.is_empty() {
+        return Err(BonsaiError::InvalidBranchName {
+            name: title.to_string(),
+            reason: "the title has no letters or digits to name a branch after".to_string(),
+            suggestion: None,
+        });
+    }
+
+    loop {
+        let name = render(&policy.template, kind, issue, &slug);
+        let over = match policy.max_length {
+            Some(max) => name.chars().count().saturating_sub(max),
+            None => 0,
+        };
+        if over == 0 || slug.is_empty() {
+            return Ok(name);
+        }
+        let length = slug.chars().count();
+        slug = shorten(&slug, length.saturating_sub(over));
+    }
+}
+
+/// Lowercase words joined by `-`. Accents are dropped (`é` becomes `e`);
+/// letters of other scripts are kept.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.nfkd().filter(|c| !is_combining_mark(*c)) {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// `slug` cut to at most `max` characters, at a `-` where possible.
+fn shorten(slug: &str, max: usize) -> String {
+    if slug.chars().count() <= max {
+        return slug.to_string();
+    }
+    let cut: String = slug.chars().take(max).collect();
+    let at_boundary = slug.chars().nth(max) == Some('-');
+    let cut = match cut.rsplit_once('-') {
+        Some((words, _)) if !at_boundary => words.to_string(),
+        _ => cut,
+    };
+    cut.trim_end_matches('-').to_string()
+}
+
+/// Substitute the placeholders, then drop separators left around empty ones.
+fn render(template: &str, kind: &str, issue: &str, slug: &str) -> String {
+    let name = template
+        .replace("{type}", kind)
+        .replace("{issue}", issue)
+        .replace("{slug}", slug);
+    name.split('/')
+        .map(|part| part.trim_matches(['-', '_', '.']))
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut tidy = String::new();
+            for c in part.chars() {
+                if !(c == '-' && tidy.ends_with('-')) {
+                    tidy.push(c);
+                }
+            }
+            tidy
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest() {
+        let policy = BranchPolicy {
+            prefixes: vec!["feature/".to_string(), "fix/".to_string()],
+            max_length: Some(20),
+            lowercase: true,
+            ..BranchPolicy::default()
+        };
+        assert_eq!(suggest(&policy, "Feature-Login Page"), "feature/login-page");
+        assert_eq!(suggest(&policy, "fix/a..b~1"), "fix/a.b-1");
+        assert_eq!(suggest(&policy, "fix//.hidden.lock"), "fix/hidden");
+        assert_eq!(
+            suggest(&policy, "feature/a-very-long-description"),
+            "feature/a-very-long"
+        );
+        assert_eq!(suggest(&BranchPolicy::default(), "Keep Case"), "Keep-Case");
+    }
+
+    #[test]
+    fn test_generate() {
+        let policy = BranchPolicy::default();
+        assert_eq!(
+            slugify("Crème brûlée: Straße & Ünïcode!"),
+            "creme-brulee-straße-unicode"
+        );
+        assert_eq!(slugify("修复 登录"), "修复-登录");
+        assert_eq!(
+            generate(
+                &policy,
+                None,
+                Some("ABC-123"),
+                Some("Fix login redirect loop")
+            )
+            .unwrap(),
+            "feature/ABC-123-fix-login-redirect-loop"
+        );
+        assert_eq!(
+            generate(&policy, Some("fix"), None, Some("Typo")).unwrap(),
+            "fix/typo"
+        );
+        assert_eq!(
+            generate(&policy, None, Some("ABC-1"), None).unwrap(),
+            "feature/ABC-1"
+        );
+        assert!(generate(&policy, None, None, Some("!!")).is_err());
+
+        let short = BranchPolicy {
+            max_length: Some(24),
+            ..BranchPolicy::default()
+        };
+        assert_eq!(
+            generate(&short, None, Some("ABC-1"), Some("Fix login redirect loop")).unwrap(),
+            "feature/ABC-1-fix-login"
+        );
+        assert_eq!(
+            generate(&short, None, Some("ABC-1"), Some("Internationalization")).unwrap(),
+            "feature/ABC-1-internatio"
+        );
+    }
+}
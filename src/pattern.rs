@@ -0,0 +1,44 @@
+/// Match `text` against a shell-style glob where `*` matches any run of
+/// characters (including `/`) and `?` matches exactly one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("release/*", "release/1.0"));
+        assert!(glob_match("*", "feature/a/b"));
+        assert!(glob_match("pr-?", "pr-1"));
+        assert!(glob_match("feat*/x*", "feature/xyz"));
+        assert!(!glob_match("release/*", "feature/release"));
+        assert!(!glob_match("pr-?", "pr-12"));
+        assert!(glob_match("main", "main"));
+        assert!(!glob_match("main", "mainline"));
+    }
+}
@@ -0,0 +1,152 @@
+use colored::Colorize;
+
+use crate::error::Result;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::WorktreeInfo;
+use crate::pattern::glob_match;
+
+/// Work that would be lost by removing a worktree (and optionally its branch).
+#[derive(Debug, Default)]
+pub struct LossReport {
+    pub uncommitted: Vec<String>,
+    pub untracked: Vec<String>,
+    /// Ignored files matching `defaults.protected_files`.
+    pub protected: Vec<String>,
+    /// Commits not on `unpushed_target`; only checked when the branch is deleted.
+    pub unpushed: Vec<String>,
+    pub unpushed_target: Option<String>,
+    /// Stashes created on the branch; only checked when the branch is deleted.
+    pub stashes: usize,
+}
+
+impl LossReport {
+    pub fn is_empty(&self) -> bool {
+        self.uncommitted.is_empty()
+            && self.untracked.is_empty()
+            && self.protected.is_empty()
+            && self.unpushed.is_empty()
+            && self.stashes == 0
+    }
+
+    /// Whether uncommitted changes and untracked files are all that is at risk.
+    pub fn is_only_local_changes(&self) -> bool {
+        self.protected.is_empty() && self.unpushed.is_empty() && self.stashes == 0
+    }
+
+    /// Drop everything a trash snapshot preserves (changes, untracked files,
+    /// the branch tip and its stashes), leaving only protected ignored files.
+    pub fn retain_not_snapshotted(&mut self) {
+        self.uncommitted.clear();
+        self.untracked.clear();
+        self.unpushed.clear();
+        self.stashes = 0;
+    }
+}
+
+/// Inspect a worktree before removal. Commits and stashes are only at risk
+/// when the branch is deleted too, so they are checked only with `with_branch`.
+pub fn check(
+    git: &GitRunner,
+    wt: &WorktreeInfo,
+    with_branch: bool,
+    configured_base: Option<&str>,
+    protected_patterns: &[String],
+) -> Result<LossReport> {
+    let mut report = LossReport {
+        uncommitted: git.uncommitted_files(&wt.path)?,
+        untracked: git.untracked_files(&wt.path)?,
+        ..Default::default()
+    };
+
+    if !protected_patterns.is_empty() {
+        report.protected = git
+            .ignored_files(&wt.path)?
+            .into_iter()
+            .filter(|f| is_protected(f, protected_patterns))
+            .collect();
+    }
+
+    if with_branch {
+        if let Some(ref branch) = wt.branch {
+            let target = git
+                .upstream(&wt.path)
+                .or_else(|| git.base_branch_for(branch, configured_base))
+                .filter(|t| t != branch);
+            if let Some(target) = target {
+                report.unpushed = git.unpushed_commits(branch, &target)?;
+                report.unpushed_target = Some(target);
+            }
+            report.stashes = git.stash_count_for(&wt.path, branch)?;
+        }
+    }
+
+    Ok(report)
+}
+
+fn is_protected(path: &str, patterns: &[String]) -> bool {
+    let trimmed = path.trim_end_matches('/');
+    let file_name = trimmed.rsplit('/').next().unwrap_or(trimmed);
+    patterns
+        .iter()
+        .any(|p| glob_match(p, trimmed) || glob_match(p, file_name))
+}
+
+pub fn print_report(name: &str, report: &LossReport, no_color: bool) {
+    if no_color {
+        eprintln!("Removing {name} would lose:");
+    } else {
+        eprintln!("Removing {} would lose:", name.cyan());
+    }
+
+    print_section("uncommitted changes", &report.uncommitted, no_color);
+    print_section("untracked files", &report.untracked, no_color);
+    print_section("protected ignored files", &report.protected, no_color);
+
+    if !report.unpushed.is_empty() {
+        let target = report.unpushed_target.as_deref().unwrap_or("upstream");
+        print_section(
+            &format!("commits not on {target}"),
+            &report.unpushed,
+            no_color,
+        );
+    }
+
+    if report.stashes > 0 {
+        if no_color {
+            eprintln!("  {} stash(es) created on this branch", report.stashes);
+        } else {
+            eprintln!(
+                "  {} stash(es) created on this branch",
+                report.stashes.to_string().red()
+            );
+        }
+    }
+}
+
+fn print_section(title: &str, items: &[String], no_color: bool) {
+    if items.is_empty() {
+        return;
+    }
+    if no_color {
+        eprintln!("  {title}:");
+    } else {
+        eprintln!("  {}:", title.red());
+    }
+    for item in items {
+        eprintln!("    {item}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_protected() {
+        let patterns = vec![".env*".to_string(), "config/*.local".to_string()];
+        assert!(is_protected(".env", &patterns));
+        assert!(is_protected("app/.env.production", &patterns));
+        assert!(is_protected("config/db.local", &patterns));
+        assert!(!is_protected("node_modules/", &patterns));
+    }
+}
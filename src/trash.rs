@@ -0,0 +1,221 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BonsaiError, Result};
+use crate::git::runner::GitRunner;
+use crate::git::worktree::WorktreeInfo;
+
+const REF_PREFIX: &str = "refs/bonsai/trash";
+
+/// Snapshot of a removed worktree, stored as refs under `refs/bonsai/trash/`
+/// plus a metadata file in the common git dir.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    pub branch: Option<String>,
+    pub path: PathBuf,
+    pub head: String,
+    /// Stash commit holding uncommitted and untracked changes, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<String>,
+    pub branch_deleted: bool,
+    pub removed_at: u64,
+}
+
+impl TrashEntry {
+    pub fn head_ref(&self) -> String {
+        format!("{REF_PREFIX}/{}/head", self.id)
+    }
+
+    pub fn changes_ref(&self) -> String {
+        format!("{REF_PREFIX}/{}/changes", self.id)
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn trash_dir(git: &GitRunner) -> Result<PathBuf> {
+    Ok(git.git_common_dir()?.join("bonsai").join("trash"))
+}
+
+fn entry_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{id}.toml"))
+}
+
+/// Save the branch tip and any uncommitted changes of `wt` before removal.
+pub fn snapshot(git: &GitRunner, wt: &WorktreeInfo, branch_deleted: bool) -> Result<TrashEntry> {
+    let removed_at = now();
+    let dir_name = wt
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "worktree".to_string());
+    let id = unique_id(&trash_dir(git)?, &format!("{dir_name}-{removed_at}"));
+
+    let changes = git.stash_changes(&wt.path, &format!("bonsai trash {id}"))?;
+
+    let entry = TrashEntry {
+        id,
+        branch: wt.branch.clone(),
+        path: wt.path.clone(),
+        head: wt.head.clone(),
+        changes,
+        branch_deleted,
+        removed_at,
+    };
+
+    if git.dry_run {
+        return Ok(entry);
+    }
+
+    // Until the trash ref exists the stash entry is the only copy of the changes
+    if let Err(e) = save(git, &entry) {
+        let _ = discard(git, &entry);
+        if let Some(ref sha) = entry.changes {
+            if let Err(restore) = git.unstash(&wt.path, sha) {
+                eprintln!(
+                    "Could not restore the changes in {} ({restore}); they are kept in `git stash list`",
+                    wt.path.display()
+                );
+            }
+        }
+        return Err(e);
+    }
+    if let Some(ref sha) = entry.changes {
+        if git.drop_stash(&wt.path, sha).is_err() {
+            eprintln!(
+                "The changes are saved in the trash but still listed in `git stash list`; drop them with `git stash drop`"
+            );
+        }
+    }
+
+    Ok(entry)
+}
+
+fn save(git: &GitRunner, entry: &TrashEntry) -> Result<()> {
+    git.update_ref(&entry.head_ref(), &entry.head)?;
+    if let Some(ref sha) = entry.changes {
+        git.update_ref(&entry.changes_ref(), sha)?;
+    }
+
+    let dir = trash_dir(git)?;
+    std::fs::create_dir_all(&dir)?;
+    let content = toml::to_string_pretty(&entry)
+        .map_err(|e| BonsaiError::Config(format!("failed to serialize trash entry: {e}")))?;
+    std::fs::write(entry_path(&dir, &entry.id), content)?;
+    Ok(())
+}
+
+/// `base`, or `base-2`, `base-3`, ... when a directory of the same name was
+/// already trashed within the same second.
+fn unique_id(dir: &Path, base: &str) -> String {
+    let mut id = base.to_string();
+    let mut n = 2;
+    while entry_path(dir, &id).exists() {
+        id = format!("{base}-{n}");
+        n += 1;
+    }
+    id
+}
+
+/// All trash entries, newest first.
+pub fn list(git: &GitRunner) -> Result<Vec<TrashEntry>> {
+    let dir = trash_dir(git)?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for file in std::fs::read_dir(&dir)? {
+        let path = file?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let entry: TrashEntry = toml::from_str(&content)
+            .map_err(|e| BonsaiError::Config(format!("failed to parse {}: {e}", path.display())))?;
+        entries.push(entry);
+    }
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.removed_at));
+    Ok(entries)
+}
+
+/// Find an entry by id, or the most recent one for a branch or directory name.
+pub fn find(git: &GitRunner, name: &str) -> Result<TrashEntry> {
+    list(git)?
+        .into_iter()
+        .find(|e| {
+            e.id == name
+                || e.branch.as_deref() == Some(name)
+                || e.path.file_name().map(|n| n.to_string_lossy()) == Some(name.into())
+        })
+        .ok_or_else(|| BonsaiError::TrashEntryNotFound {
+            name: name.to_string(),
+        })
+}
+
+/// Delete an entry's refs and metadata.
+pub fn discard(git: &GitRunner, entry: &TrashEntry) -> Result<()> {
+    git.delete_ref(&entry.head_ref())?;
+    if entry.changes.is_some() {
+        git.delete_ref(&entry.changes_ref())?;
+    }
+    if git.dry_run {
+        return Ok(());
+    }
+    let path = entry_path(&trash_dir(git)?, &entry.id);
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Parse an age such as `30m`, `12h`, `7d` or `2w` into seconds.
+pub fn parse_age(input: &str) -> Option<u64> {
+    let input = input.trim();
+    let split = input.find(|c: char| !c.is_ascii_digit())?;
+    let (n, unit) = input.split_at(split);
+    let n: u64 = n.parse().ok()?;
+    let secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(n * secs)
+}
+
+/// Render an age in seconds as e.g. `5m ago` or `3d ago`.
+pub fn format_age(secs: u64) -> String {
+    match secs {
+        s if s < 60 => "just now".to_string(),
+        s if s < 60 * 60 => format!("{}m ago", s / 60),
+        s if s < 24 * 60 * 60 => format!("{}h ago", s / (60 * 60)),
+        s => format!("{}d ago", s / (24 * 60 * 60)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30m"), Some(30 * 60));
+        assert_eq!(parse_age("7d"), Some(7 * 24 * 60 * 60));
+        assert_eq!(parse_age("2w"), Some(14 * 24 * 60 * 60));
+        assert_eq!(parse_age("7"), None);
+        assert_eq!(parse_age("d"), None);
+        assert_eq!(parse_age("3y"), None);
+    }
+}
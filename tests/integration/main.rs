@@ -1,12 +1,30 @@
 mod helpers;
 
 mod test_add;
+mod test_adopt;
+mod test_bare;
 mod test_cd;
+mod test_clone;
+mod test_config;
+mod test_deinit;
+mod test_doctor;
+mod test_errors;
+mod test_exec;
 mod test_init;
+mod test_linked_worktree;
 mod test_list;
 mod test_lock;
 mod test_move;
+mod test_naming;
+mod test_profile;
+mod test_prompt;
+mod test_prune;
 mod test_remove;
 mod test_rename;
+mod test_schema;
 mod test_shell_init;
 mod test_status;
+mod test_switch;
+mod test_sync;
+mod test_trash;
+mod test_undo;
@@ -91,3 +91,153 @@ fn test_add_duplicate_fails() {
         .unwrap();
     assert!(!output.status.success());
 }
+
+#[test]
+fn test_add_create_uses_configured_base_branch() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    run_git(&repo, &["checkout", "-b", "develop"]);
+    run_git(&repo, &["commit", "--allow-empty", "-m", "develop commit"]);
+    run_git(&repo, &["checkout", "main"]);
+
+    let config = std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap();
+    let config = config.replace("[defaults]\n", "[defaults]\nbase_branch = \"develop\"\n");
+    std::fs::write(repo.join(".bonsai.toml"), config).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/from-config"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let develop = run_git(&repo, &["rev-parse", "develop"]);
+    let feature = run_git(&repo, &["rev-parse", "feature/from-config"]);
+    assert_eq!(feature, develop);
+}
+
+#[test]
+fn test_add_create_uses_remote_head_as_base() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    run_git(&repo, &["checkout", "-b", "trunk"]);
+    run_git(&repo, &["commit", "--allow-empty", "-m", "trunk commit"]);
+    run_git(&repo, &["checkout", "main"]);
+
+    // Simulate a clone whose remote HEAD points at `trunk`
+    let repo_str = repo.to_string_lossy().to_string();
+    run_git(&repo, &["remote", "add", "origin", &repo_str]);
+    run_git(&repo, &["update-ref", "refs/remotes/origin/trunk", "trunk"]);
+    run_git(
+        &repo,
+        &[
+            "symbolic-ref",
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/trunk",
+        ],
+    );
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/from-remote-head"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let trunk = run_git(&repo, &["rev-parse", "trunk"]);
+    let feature = run_git(&repo, &["rev-parse", "feature/from-remote-head"]);
+    assert_eq!(feature, trunk);
+}
+
+#[test]
+fn test_add_from_title_generates_branch_and_records_issue() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args([
+            "add",
+            "--from-title",
+            "Fix login redirect loop",
+            "--issue",
+            "ABC-123",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let branch = "feature/ABC-123-fix-login-redirect-loop";
+    assert!(repo
+        .join(".bonsai/feature-ABC-123-fix-login-redirect-loop")
+        .is_dir());
+    assert_eq!(
+        run_git(&repo, &["config", &format!("branch.{branch}.bonsai-issue")]),
+        "ABC-123"
+    );
+
+    let output = bonsai_cmd(&repo).args(["list"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("ISSUE"), "list output: {stdout}");
+    assert!(stdout.contains("ABC-123"), "list output: {stdout}");
+
+    let output = bonsai_cmd(&repo).args(["status", branch]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Issue: ABC-123"), "status output: {stdout}");
+}
+
+#[test]
+fn test_add_from_title_uses_configured_template() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(
+        repo.join(".bonsai.toml"),
+        "version = 2\n\n[branches]\ntemplate = \"{issue}/{type}-{slug}\"\nmax_length = 30\n",
+    )
+    .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args([
+            "add",
+            "--from-title",
+            "Crème brûlée: über-long résumé parsing",
+            "--issue",
+            "ABC-7",
+            "--type",
+            "fix",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    run_git(
+        &repo,
+        &["rev-parse", "--verify", "ABC-7/fix-creme-brulee-uber"],
+    );
+}
+
+#[test]
+fn test_list_has_no_issue_column_without_issues() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo).args(["list"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("ISSUE"), "list output: {stdout}");
+}
@@ -0,0 +1,117 @@
+use crate::helpers::*;
+
+#[test]
+fn test_adopt_all_moves_foreign_worktrees() {
+    let (tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    let foreign = tmp.path().join("elsewhere");
+    run_git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "feature/old",
+            foreign.to_str().unwrap(),
+        ],
+    );
+
+    let output = bonsai_cmd(&repo)
+        .args(["adopt", "--all", "--yes"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "adopt failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(!foreign.exists());
+    assert!(repo.join(".bonsai/feature-old").is_dir());
+    assert_eq!(
+        run_git(&repo, &["config", "branch.feature/old.bonsai-base"]),
+        "main"
+    );
+
+    // Adopting is undoable like any other move
+    let output = bonsai_cmd(&repo).args(["undo"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "undo failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(foreign.is_dir());
+}
+
+#[test]
+fn test_adopt_path_repairs_hand_moved_worktree() {
+    let (tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    let original = tmp.path().join("original");
+    let moved = tmp.path().join("moved");
+    run_git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "feature/lost",
+            original.to_str().unwrap(),
+        ],
+    );
+    std::fs::rename(&original, &moved).unwrap();
+
+    // Declining leaves git's records as they were
+    let output = bonsai_cmd(&repo)
+        .args(["adopt", moved.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Aborted."));
+    let list = run_git(&repo, &["worktree", "list", "--porcelain"]);
+    assert!(list.contains("original"), "worktree list: {list}");
+
+    let output = bonsai_cmd(&repo)
+        .args(["adopt", moved.to_str().unwrap(), "--yes"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "adopt failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let list = run_git(&repo, &["worktree", "list", "--porcelain"]);
+    assert!(
+        list.contains(".bonsai/feature-lost"),
+        "worktree list: {list}"
+    );
+    assert!(!list.contains("prunable"), "worktree list: {list}");
+
+    // Undo returns it to where it was found
+    let output = bonsai_cmd(&repo).args(["undo"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "undo failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(moved.is_dir());
+    let list = run_git(&repo, &["worktree", "list", "--porcelain"]);
+    assert!(list.contains("moved"), "worktree list: {list}");
+}
+
+#[test]
+fn test_adopt_nothing_to_do() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/managed"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["adopt", "--all", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Nothing to adopt"));
+}
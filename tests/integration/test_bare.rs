@@ -0,0 +1,194 @@
+use tempfile::TempDir;
+
+use crate::helpers::*;
+
+#[test]
+fn test_clone_bare_sets_up_worktree_layout() {
+    let (_origin_tmp, origin) = setup_repo();
+    let tmp = TempDir::new().unwrap();
+    let url = format!("file://{}", origin.display());
+
+    let output = bonsai_cmd(tmp.path())
+        .args(["clone", "--bare", &url, "proj"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "clone failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let proj = tmp.path().join("proj");
+    assert!(proj.join(".bare").is_dir());
+    assert!(proj.join(".bonsai.toml").is_file());
+    assert!(proj.join("main").join(".git").is_file());
+    assert_eq!(
+        run_git(&proj, &["rev-parse", "--abbrev-ref", "main@{upstream}"]),
+        "origin/main"
+    );
+
+    // Worktrees land next to the default branch, whichever worktree runs `add`
+    let output = bonsai_cmd(&proj.join("main"))
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(proj.join("feature-x").is_dir());
+
+    let output = bonsai_cmd(&proj)
+        .args(["list", "--names-only"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("feature/x"), "list output: {stdout}");
+    assert!(stdout.contains("main"), "list output: {stdout}");
+
+    let output = bonsai_cmd(&proj)
+        .args(["remove", "feature/x"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "remove failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!proj.join("feature-x").exists());
+}
+
+#[test]
+fn test_init_in_bare_repo_keeps_config_in_repo() {
+    let (_origin_tmp, origin) = setup_repo();
+    let tmp = TempDir::new().unwrap();
+    let bare = tmp.path().join("project.git");
+    run_git(
+        tmp.path(),
+        &["clone", "--bare", origin.to_str().unwrap(), "project.git"],
+    );
+
+    let output = bonsai_cmd(&bare).args(["init"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(bare.join("bonsai.toml").is_file());
+    assert!(!tmp.path().join(".gitignore").exists());
+
+    let output = bonsai_cmd(&bare)
+        .args(["add", "-c", "feature/y"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let worktree = tmp.path().join("project.worktrees/feature-y");
+    assert!(worktree.is_dir());
+
+    // The bare repository itself is not listed as a worktree
+    let output = bonsai_cmd(&worktree)
+        .args(["list", "--porcelain"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("project.git"), "list output: {stdout}");
+    assert!(stdout.contains("feature-y"), "list output: {stdout}");
+}
+
+/// `clone --bare` of a fresh origin, with a `feature/x` worktree added.
+fn bare_clone_with_feature(tmp: &TempDir, origin: &std::path::Path) -> std::path::PathBuf {
+    let url = format!("file://{}", origin.display());
+    bonsai_cmd(tmp.path())
+        .args(["clone", "--bare", &url, "proj"])
+        .output()
+        .unwrap();
+    let proj = tmp.path().join("proj");
+    let output = bonsai_cmd(&proj)
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    proj
+}
+
+#[test]
+fn test_status_and_sync_in_bare_layout() {
+    let (_origin_tmp, origin) = setup_repo();
+    let tmp = TempDir::new().unwrap();
+    let proj = bare_clone_with_feature(&tmp, &origin);
+
+    let output = bonsai_cmd(&proj)
+        .args(["list", "--status"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("clean"), "list output: {stdout}");
+    assert!(!stdout.contains(" ?"), "list output: {stdout}");
+
+    let output = bonsai_cmd(&proj)
+        .args(["status", "feature/x"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Status: clean"), "status output: {stdout}");
+
+    let output = bonsai_cmd(&proj)
+        .args(["sync", "--all", "--no-fetch"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "sync failed: {stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        !stdout.contains("status unavailable"),
+        "sync output: {stdout}"
+    );
+}
+
+#[test]
+fn test_remove_with_branch_in_bare_layout() {
+    let (_origin_tmp, origin) = setup_repo();
+    let tmp = TempDir::new().unwrap();
+    let proj = bare_clone_with_feature(&tmp, &origin);
+    std::fs::write(proj.join("feature-x/notes.txt"), "draft").unwrap();
+
+    let output = bonsai_cmd(&proj)
+        .args([
+            "remove",
+            "feature/x",
+            "--with-branch",
+            "--discard-unpushed",
+            "--trash",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "remove failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!proj.join("feature-x").exists());
+
+    // The untracked file was kept in the trash and comes back on restore
+    let output = bonsai_cmd(&proj)
+        .args(["restore", "feature/x"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "restore failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        std::fs::read_to_string(proj.join("feature-x/notes.txt")).unwrap(),
+        "draft"
+    );
+}
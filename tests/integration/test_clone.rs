@@ -0,0 +1,155 @@
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+use crate::helpers::*;
+
+const TEAM_CONFIG: &str = r#"version = "1"
+
+[defaults]
+worktree_dir = ".bonsai"
+
+[hooks]
+post_create = [{ type = "command", command = "touch hooked" }]
+
+[clone]
+branches = ["main", "develop"]
+"#;
+
+/// Origin repository with `main` and `develop`, and its file:// URL.
+fn setup_origin() -> (TempDir, PathBuf, String) {
+    let (tmp, origin) = setup_repo();
+    run_git(&origin, &["branch", "develop"]);
+    let url = format!("file://{}", origin.display());
+    (tmp, origin, url)
+}
+
+#[test]
+fn test_clone_with_template_creates_configured_worktrees() {
+    let (_origin_tmp, _origin, url) = setup_origin();
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("team.toml"), TEAM_CONFIG).unwrap();
+
+    let output = bonsai_cmd(tmp.path())
+        .args(["clone", &url, "work", "--config", "team.toml"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "clone failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let work = tmp.path().join("work");
+    assert_eq!(
+        std::fs::read_to_string(work.join(".bonsai.toml")).unwrap(),
+        TEAM_CONFIG
+    );
+    assert!(run_git(&work, &["check-ignore", ".bonsai/"]).contains(".bonsai"));
+
+    // `main` is the main worktree itself; only `develop` gets a new one
+    assert!(!work.join(".bonsai/main").exists());
+    let develop = work.join(".bonsai/develop");
+    assert!(develop.join("hooked").is_file());
+    assert_eq!(
+        run_git(&work, &["rev-parse", "--abbrev-ref", "develop@{upstream}"]),
+        "origin/develop"
+    );
+}
+
+#[test]
+fn test_clone_bare_uses_committed_config() {
+    let (_origin_tmp, origin, url) = setup_origin();
+    std::fs::write(origin.join(".bonsai.toml"), TEAM_CONFIG).unwrap();
+    run_git(&origin, &["add", ".bonsai.toml"]);
+    run_git(&origin, &["commit", "-m", "add bonsai config"]);
+    let tmp = TempDir::new().unwrap();
+
+    let output = bonsai_cmd(tmp.path())
+        .args(["clone", "--bare", &url])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "clone failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The directory is named after the repository, as with `git clone`
+    let name = origin.file_name().unwrap();
+    let proj = tmp.path().join(name);
+    assert!(proj.join("main/hooked").is_file());
+    assert!(proj.join("develop/hooked").is_file());
+    let config = std::fs::read_to_string(proj.join(".bonsai.toml")).unwrap();
+    assert!(config.contains("worktree_dir = \".\""), "config: {config}");
+}
+
+#[test]
+fn test_clone_skips_missing_branches() {
+    let (_origin_tmp, _origin, url) = setup_origin();
+    let tmp = TempDir::new().unwrap();
+    let template = TEAM_CONFIG.replace("\"develop\"", "\"nope\"");
+    std::fs::write(tmp.path().join("team.toml"), template).unwrap();
+
+    let output = bonsai_cmd(tmp.path())
+        .args(["clone", &url, "work", "--config", "team.toml", "--no-hooks"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Skipping nope"), "stderr: {stderr}");
+    assert!(!tmp.path().join("work/.bonsai/nope").exists());
+}
+
+#[test]
+fn test_clone_rejects_invalid_template_before_cloning() {
+    let (_origin_tmp, _origin, url) = setup_origin();
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("team.toml"), "defaults = 3\n").unwrap();
+
+    let output = bonsai_cmd(tmp.path())
+        .args(["clone", &url, "work", "--config", "team.toml"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(!tmp.path().join("work").exists());
+}
+
+#[test]
+fn test_clone_refuses_non_empty_destination() {
+    let (_origin_tmp, _origin, url) = setup_origin();
+    let tmp = TempDir::new().unwrap();
+    std::fs::create_dir(tmp.path().join("work")).unwrap();
+    std::fs::write(tmp.path().join("work/keep.txt"), "mine\n").unwrap();
+
+    let output = bonsai_cmd(tmp.path())
+        .args(["clone", &url, "work", "--bare"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(14));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("already exists"), "{stderr}");
+    assert!(!tmp.path().join("work/.bare").exists());
+}
+
+#[test]
+fn test_clone_leaves_a_clean_checkout() {
+    let (_origin_tmp, origin, url) = setup_origin();
+    std::fs::write(origin.join(".bonsai.toml"), TEAM_CONFIG).unwrap();
+    run_git(&origin, &["add", ".bonsai.toml"]);
+    run_git(&origin, &["commit", "-m", "share bonsai config"]);
+    let tmp = TempDir::new().unwrap();
+
+    let output = bonsai_cmd(tmp.path())
+        .args(["clone", &url, "work", "--no-hooks"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "clone failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let work = tmp.path().join("work");
+    assert_eq!(run_git(&work, &["status", "--porcelain"]), "");
+    assert!(run_git(&work, &["check-ignore", ".bonsai/"]).contains(".bonsai"));
+}
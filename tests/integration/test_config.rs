@@ -0,0 +1,189 @@
+use crate::helpers::*;
+
+const COMMENTED_V1: &str = r#"version = "1" # schema
+
+[defaults]
+# Where worktrees go
+worktree_dir = ".bonsai"
+"#;
+
+#[test]
+fn test_unknown_key_reports_location() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(
+        repo.join(".bonsai.toml"),
+        "version = 2\n\n[hooks]\npost_craete = []\n",
+    )
+    .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(".bonsai.toml:4:1:"), "stderr: {stderr}");
+    assert!(
+        stderr.contains("unknown field `post_craete`"),
+        "stderr: {stderr}"
+    );
+    assert!(stderr.contains("4 | post_craete = []"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_newer_schema_version_is_rejected() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), "version = 99\n").unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "validate"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("schema version 99"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_old_schema_loads_and_migrates_keeping_comments() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), COMMENTED_V1).unwrap();
+
+    // Version 1 files keep working before they are migrated
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "migrate"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap(),
+        COMMENTED_V1.replace(r#"version = "1""#, "version = 2")
+    );
+}
+
+#[test]
+fn test_set_keeps_comments_and_rejects_unknown_keys() {
+    let (_tmp, repo) = setup_repo();
+    let original = COMMENTED_V1.replace(r#"version = "1""#, "version = 2");
+    std::fs::write(repo.join(".bonsai.toml"), &original).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "set", "defaults.worktree_dir", "../trees"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let content = std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap();
+    assert_eq!(content, original.replace(".bonsai", "../trees"));
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "set", "defaults.worktre_dir", "x"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    assert_eq!(
+        std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap(),
+        content
+    );
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "set", "prompt.status_ttl", "30"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let content = std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap();
+    assert!(
+        content.ends_with("\n[prompt]\nstatus_ttl = 30\n"),
+        "config: {content}"
+    );
+}
+
+#[test]
+fn test_get_and_show_include_defaults() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "get", "prompt.status_ttl"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "5\n");
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "get", "defaults.worktree_dir"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), ".bonsai\n");
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "get", "defaults.base_branch"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+
+    let output = bonsai_cmd(&repo).args(["config", "show"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[prompt]"), "show output: {stdout}");
+    assert!(stdout.contains("version = 2"), "show output: {stdout}");
+}
+
+#[test]
+fn test_edit_validates_result() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "edit"])
+        .env("VISUAL", "true")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "edit"])
+        .env("VISUAL", "echo 'typo = 1' >>")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown field `typo`"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_remove_refuses_to_run_with_broken_config() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".gitignore"), ".env\n.bonsai/\n").unwrap();
+    std::fs::write(
+        repo.join(".bonsai.toml"),
+        "version = 2\n\n[defaults]\nworktree_dir = \".bonsai\"\nprotected_files = [\".env\"]\n",
+    )
+    .unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+    let wt = repo.join(".bonsai/feature-x");
+    std::fs::write(wt.join(".env"), "SECRET=1\n").unwrap();
+
+    // A typo elsewhere must not drop `protected_files`
+    let mut content = std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap();
+    content.push_str("\n[prompt]\ntemplat = \"{name}\"\n");
+    std::fs::write(repo.join(".bonsai.toml"), content).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "feature/x"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unknown field `templat`"),
+        "stderr: {stderr}"
+    );
+    assert!(wt.join(".env").exists());
+}
@@ -0,0 +1,90 @@
+use crate::helpers::*;
+
+#[test]
+fn test_deinit_reverses_init() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".gitignore"), "target/\n").unwrap();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["deinit", "--yes"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "deinit failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(!repo.join(".bonsai.toml").exists());
+    assert!(!repo.join(".bonsai").exists());
+    assert_eq!(
+        std::fs::read_to_string(repo.join(".gitignore")).unwrap(),
+        "target/\n"
+    );
+}
+
+#[test]
+fn test_deinit_removes_info_exclude_entry_and_worktrees() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo)
+        .args(["init", "--exclude-mode", "info-exclude"])
+        .output()
+        .unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["deinit", "--yes", "--remove-worktrees"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "deinit failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(!repo.join(".bonsai").exists());
+    assert!(!repo.join(".gitignore").exists());
+    let exclude = std::fs::read_to_string(repo.join(".git/info/exclude")).unwrap();
+    assert!(!exclude.contains(".bonsai/"), "exclude: {exclude}");
+    assert_eq!(run_git(&repo, &["status", "--porcelain"]), "");
+    // The branch survives; only the worktree goes
+    run_git(&repo, &["rev-parse", "--verify", "feature/x"]);
+}
+
+#[test]
+fn test_deinit_keeps_worktrees_and_their_ignore_entry() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["deinit", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    assert!(!repo.join(".bonsai.toml").exists());
+    assert!(repo.join(".bonsai/feature-x").is_dir());
+    assert!(std::fs::read_to_string(repo.join(".gitignore"))
+        .unwrap()
+        .contains(".bonsai/"));
+}
+
+#[test]
+fn test_deinit_requires_init() {
+    let (_tmp, repo) = setup_repo();
+    let output = bonsai_cmd(&repo)
+        .args(["deinit", "--yes"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not initialized"), "stderr: {stderr}");
+}
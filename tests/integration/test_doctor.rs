@@ -0,0 +1,140 @@
+use crate::helpers::*;
+
+#[test]
+fn test_doctor_clean_repo() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/healthy"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo).args(["doctor"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "doctor failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No problems found"));
+}
+
+#[test]
+fn test_doctor_prunes_deleted_worktree() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/gone"])
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(repo.join(".bonsai/feature-gone")).unwrap();
+
+    let output = bonsai_cmd(&repo).args(["doctor"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(60));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("feature-gone"), "stderr: {stderr}");
+    assert!(stderr.contains("git worktree prune"), "stderr: {stderr}");
+
+    let output = bonsai_cmd(&repo)
+        .args(["doctor", "--fix"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "doctor --fix failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let list = run_git(&repo, &["worktree", "list"]);
+    assert!(!list.contains("feature-gone"), "worktree list: {list}");
+}
+
+#[test]
+fn test_doctor_repairs_moved_worktree() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/moved"])
+        .output()
+        .unwrap();
+    std::fs::rename(
+        repo.join(".bonsai/feature-moved"),
+        repo.join(".bonsai/elsewhere"),
+    )
+    .unwrap();
+
+    let output = bonsai_cmd(&repo).args(["doctor"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(60));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("git worktree repair"));
+
+    let output = bonsai_cmd(&repo)
+        .args(["doctor", "--fix"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "doctor --fix failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The branch is still checked out, now at the new location
+    let list = run_git(&repo, &["worktree", "list", "--porcelain"]);
+    assert!(list.contains("elsewhere"), "worktree list: {list}");
+    assert!(
+        list.contains("refs/heads/feature/moved"),
+        "worktree list: {list}"
+    );
+    assert!(!list.contains("prunable"), "worktree list: {list}");
+}
+
+#[test]
+fn test_doctor_fixes_gitignore() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    std::fs::write(repo.join(".gitignore"), "").unwrap();
+
+    let output = bonsai_cmd(&repo).args(["doctor"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(60));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not ignored"));
+
+    let output = bonsai_cmd(&repo)
+        .args(["doctor", "--fix"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let gitignore = std::fs::read_to_string(repo.join(".gitignore")).unwrap();
+    assert!(gitignore.contains(".bonsai/"));
+}
+
+#[test]
+fn test_doctor_reports_unregistered_worktree_and_fixes_the_rest() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    for branch in ["feature/gone", "feature/lost"] {
+        bonsai_cmd(&repo)
+            .args(["add", "-c", branch])
+            .output()
+            .unwrap();
+    }
+    std::fs::remove_dir_all(repo.join(".bonsai/feature-gone")).unwrap();
+    // git forgets the worktree entirely, but its checkout stays behind
+    std::fs::remove_dir_all(repo.join(".git/worktrees/feature-lost")).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["doctor", "--fix"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(60));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("not registered with git"),
+        "stderr: {stderr}"
+    );
+    assert!(!stderr.contains("git worktree repair"), "stderr: {stderr}");
+    assert!(
+        stderr.contains("Fixed: git worktree prune"),
+        "stderr: {stderr}"
+    );
+
+    let list = run_git(&repo, &["worktree", "list"]);
+    assert!(!list.contains("feature-gone"), "worktree list: {list}");
+    assert!(repo.join(".bonsai/feature-lost").exists());
+}
@@ -0,0 +1,72 @@
+use crate::helpers::*;
+
+#[test]
+fn test_not_found_exit_code() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "nonexistent"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(20));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Error: worktree 'nonexistent' not found"));
+}
+
+#[test]
+fn test_not_in_repository_exit_code() {
+    let tmp = tempfile::TempDir::new().unwrap();
+
+    let output = bonsai_cmd(tmp.path()).args(["list"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(10));
+}
+
+#[test]
+fn test_error_format_json() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["--error-format", "json", "cd", "nonexistent"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(20));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let value: serde_json::Value = serde_json::from_str(stderr.trim()).unwrap();
+    assert_eq!(value["kind"], "worktree_not_found");
+    assert_eq!(value["message"], "worktree 'nonexistent' not found");
+    assert_eq!(value["details"]["name"], "nonexistent");
+}
+
+#[test]
+fn test_git_failure_is_classified() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/locked"])
+        .output()
+        .unwrap();
+    bonsai_cmd(&repo)
+        .args(["lock", "feature/locked"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args([
+            "--error-format",
+            "json",
+            "move",
+            "feature/locked",
+            "elsewhere",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(41));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let value: serde_json::Value = serde_json::from_str(stderr.trim()).unwrap();
+    assert_eq!(value["kind"], "git_command_failed");
+    assert_eq!(value["details"]["cause"], "worktree_locked");
+}
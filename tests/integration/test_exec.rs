@@ -0,0 +1,141 @@
+use crate::helpers::*;
+
+#[test]
+fn test_exec_runs_in_each_worktree_with_env() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/exec"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args([
+            "exec",
+            "--",
+            "sh",
+            "-c",
+            "echo \"$BONSAI_BRANCH $(basename \"$PWD\")\"",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "exec failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[main] main"), "{stdout}");
+    assert!(
+        stdout.contains("[feature/exec] feature/exec feature-exec"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_exec_filter_and_group_output() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/a"])
+        .output()
+        .unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "bugfix/b"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args([
+            "exec",
+            "--filter",
+            "feature/*",
+            "--output",
+            "group",
+            "--parallel",
+            "2",
+            "--",
+            "git",
+            "rev-parse",
+            "--abbrev-ref",
+            "HEAD",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("==> feature/a"), "{stdout}");
+    assert!(!stdout.contains("bugfix/b"), "{stdout}");
+}
+
+#[test]
+fn test_exec_reports_failures() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/fails"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["exec", "--", "sh", "-c", "test \"$BONSAI_BRANCH\" = main"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("FAIL feature/fails"), "{stderr}");
+    assert!(stderr.contains("command failed in 1 worktree(s)"));
+}
+
+#[test]
+fn test_exec_fail_fast_stops_early() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/skipped"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["exec", "--fail-fast", "--", "false"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not run (fail-fast)"), "{stderr}");
+    assert!(stderr.contains("Skipped 1 worktree(s)"), "{stderr}");
+    // Only the worktree that ran counts as failed
+    assert!(
+        stderr.contains("command failed in 1 worktree(s)"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_exec_keep_going_runs_everywhere() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/second"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["exec", "--keep-going", "--", "false"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(52));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("command failed in 2 worktree(s)"),
+        "{stderr}"
+    );
+
+    let output = bonsai_cmd(&repo)
+        .args(["exec", "--keep-going", "--fail-fast", "--", "true"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"), "{stderr}");
+}
@@ -68,3 +68,147 @@ fn test_init_skips_duplicate_gitignore_entry() {
     let count = gitignore.matches(".bonsai/").count();
     assert_eq!(count, 1);
 }
+
+#[test]
+fn test_init_yes_adds_detected_hooks() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join("package.json"), "{}\n").unwrap();
+    std::fs::write(repo.join("pnpm-lock.yaml"), "").unwrap();
+    std::fs::write(repo.join(".envrc"), "").unwrap();
+    std::fs::write(repo.join(".env"), "SECRET=1\n").unwrap();
+    std::fs::write(repo.join(".env.example"), "SECRET=\n").unwrap();
+    std::fs::write(repo.join(".gitignore"), ".env\n").unwrap();
+
+    let output = bonsai_cmd(&repo).args(["init", "--yes"]).output().unwrap();
+    assert!(output.status.success());
+
+    let config = std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap();
+    assert!(config.contains("from = \".env\""), "config: {config}");
+    assert!(!config.contains(".env.example"), "config: {config}");
+    assert!(
+        config.contains("command = \"pnpm install\""),
+        "config: {config}"
+    );
+    assert!(
+        config.contains("command = \"direnv allow\""),
+        "config: {config}"
+    );
+}
+
+#[test]
+fn test_init_without_terminal_only_suggests_hooks() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join("go.mod"), "module example\n").unwrap();
+
+    let output = bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("go mod download"), "stderr: {stderr}");
+
+    let config = std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap();
+    assert!(!config.contains("go mod download"), "config: {config}");
+}
+
+#[test]
+fn test_init_template_by_name() {
+    let (tmp, repo) = setup_repo();
+    let config_home = tmp.path().join("xdg");
+    std::fs::create_dir_all(config_home.join("bonsai/templates")).unwrap();
+    let template = "# Team defaults\nversion = 2\n\n[defaults]\nworktree_dir = \"trees\"\n";
+    std::fs::write(config_home.join("bonsai/templates/team.toml"), template).unwrap();
+    // Detected hooks do not apply on top of a template
+    std::fs::write(repo.join("go.mod"), "module example\n").unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["init", "--template", "team"])
+        .env("XDG_CONFIG_HOME", &config_home)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap(),
+        template
+    );
+    assert!(repo.join("trees").is_dir());
+    let gitignore = std::fs::read_to_string(repo.join(".gitignore")).unwrap();
+    assert!(gitignore.contains("trees/"));
+}
+
+#[test]
+fn test_init_rejects_invalid_template() {
+    let (tmp, repo) = setup_repo();
+    let template = tmp.path().join("bad.toml");
+    std::fs::write(&template, "version = 2\nworktree_dir = \"x\"\n").unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["init", "--template", template.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    assert!(!repo.join(".bonsai.toml").exists());
+}
+
+#[test]
+fn test_init_info_exclude_leaves_gitignore_alone() {
+    let (_tmp, repo) = setup_repo();
+
+    let output = bonsai_cmd(&repo)
+        .args(["init", "--exclude-mode", "info-exclude"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    assert!(!repo.join(".gitignore").exists());
+    let exclude = std::fs::read_to_string(repo.join(".git/info/exclude")).unwrap();
+    assert!(
+        exclude.lines().any(|l| l == ".bonsai/"),
+        "exclude: {exclude}"
+    );
+    assert_eq!(
+        run_git(&repo, &["status", "--porcelain"]),
+        "?? .bonsai.toml"
+    );
+}
+
+#[test]
+fn test_init_exclude_mode_none_is_respected_by_doctor() {
+    let (_tmp, repo) = setup_repo();
+
+    let output = bonsai_cmd(&repo)
+        .args(["init", "--exclude-mode", "none"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!repo.join(".gitignore").exists());
+
+    let output = bonsai_cmd(&repo).args(["doctor"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "doctor failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_init_ignores_custom_worktree_dir() {
+    let (tmp, repo) = setup_repo();
+    let template = tmp.path().join("team.toml");
+    std::fs::write(
+        &template,
+        "version = 2\n[defaults]\nworktree_dir = \"wt/\"\n",
+    )
+    .unwrap();
+
+    bonsai_cmd(&repo)
+        .args(["init", "--template", template.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    let gitignore = std::fs::read_to_string(repo.join(".gitignore")).unwrap();
+    assert_eq!(gitignore, "wt/\n");
+}
@@ -0,0 +1,114 @@
+use std::path::{Path, PathBuf};
+
+use crate::helpers::*;
+
+/// Initialized repo with `feature/a` checked out in a linked worktree.
+fn setup_linked() -> (tempfile::TempDir, PathBuf, PathBuf) {
+    let (tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/a"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let linked = repo.join(".bonsai/feature-a");
+    (tmp, repo, linked)
+}
+
+fn bonsai_ok(dir: &Path, args: &[&str]) -> String {
+    let output = bonsai_cmd(dir).args(args).output().unwrap();
+    assert!(
+        output.status.success(),
+        "bonsai {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_init_from_linked_worktree_writes_main_config() {
+    let (tmp, repo) = setup_repo();
+    let linked = tmp.path().join("linked");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "side", linked.to_str().unwrap()],
+    );
+
+    bonsai_ok(&linked, &["init"]);
+
+    assert!(repo.join(".bonsai.toml").is_file());
+    assert!(!linked.join(".bonsai.toml").exists());
+    assert!(!linked.join(".bonsai").exists());
+}
+
+#[test]
+fn test_add_from_linked_worktree_uses_main_layout() {
+    let (_tmp, repo, linked) = setup_linked();
+    std::fs::write(repo.join(".env"), "SECRET=1\n").unwrap();
+    let config = std::fs::read_to_string(repo.join(".bonsai.toml"))
+        .unwrap()
+        .replace(
+            "post_create = []",
+            r#"post_create = [{ type = "copy", from = ".env", to = ".env" }]"#,
+        );
+    std::fs::write(repo.join(".bonsai.toml"), config).unwrap();
+
+    bonsai_ok(&linked, &["add", "-c", "feature/b"]);
+
+    let created = repo.join(".bonsai/feature-b");
+    assert!(created.is_dir());
+    assert!(!linked.join(".bonsai").exists());
+    // Hooks copy from the main worktree, not the one bonsai ran in
+    assert_eq!(
+        std::fs::read_to_string(created.join(".env")).unwrap(),
+        "SECRET=1\n"
+    );
+}
+
+#[test]
+fn test_read_only_commands_from_linked_worktree() {
+    let (_tmp, repo, linked) = setup_linked();
+
+    let names = bonsai_ok(&linked, &["list", "--names-only"]);
+    assert!(names.lines().any(|l| l == "main"), "list output: {names}");
+    assert!(
+        names.lines().any(|l| l == "feature/a"),
+        "list output: {names}"
+    );
+
+    let status = bonsai_ok(&linked, &["status", "main"]);
+    assert!(status.contains("main"), "status output: {status}");
+
+    let main = bonsai_ok(&linked, &["cd", "@"]);
+    assert_eq!(main.trim(), repo.to_str().unwrap());
+
+    let exec = bonsai_ok(&linked, &["exec", "--", "git", "rev-parse", "HEAD"]);
+    assert!(!exec.is_empty());
+
+    bonsai_ok(&linked, &["doctor"]);
+}
+
+#[test]
+fn test_managing_commands_from_linked_worktree() {
+    let (_tmp, repo, linked) = setup_linked();
+    bonsai_ok(&repo, &["add", "-c", "feature/b"]);
+
+    bonsai_ok(&linked, &["rename", "feature/b", "feature/c"]);
+    assert!(repo.join(".bonsai/feature-c").is_dir());
+    assert!(!repo.join(".bonsai/feature-b").exists());
+
+    bonsai_ok(&linked, &["lock", "feature/c"]);
+    bonsai_ok(&linked, &["unlock", "feature/c"]);
+
+    let moved = repo.join(".bonsai/elsewhere");
+    bonsai_ok(&linked, &["move", "feature/c", moved.to_str().unwrap()]);
+    assert!(moved.is_dir());
+
+    bonsai_ok(&linked, &["remove", "feature/c"]);
+    assert!(!moved.exists());
+
+    bonsai_ok(&linked, &["prune", "--yes"]);
+    bonsai_ok(&linked, &["undo"]);
+    assert!(moved.is_dir());
+}
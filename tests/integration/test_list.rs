@@ -54,3 +54,24 @@ fn test_list_names_only() {
     assert!(names.contains(&"main"));
     assert!(names.contains(&"feature/names"));
 }
+
+#[test]
+fn test_list_status_shows_base_comparison() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/ahead"])
+        .output()
+        .unwrap();
+
+    let wt = repo.join(".bonsai/feature-ahead");
+    run_git(&wt, &["commit", "--allow-empty", "-m", "feature work"]);
+
+    let output = bonsai_cmd(&repo)
+        .args(["list", "--status"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[main ⇡1]"), "{stdout}");
+}
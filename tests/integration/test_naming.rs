@@ -0,0 +1,122 @@
+use crate::helpers::*;
+
+const POLICY: &str = r#"version = 2
+
+[branches]
+pattern = '^[a-z]+/[A-Z]+-[0-9]+-[a-z0-9-]+$'
+prefixes = ["feature/", "fix/"]
+max_length = 40
+"#;
+
+#[test]
+fn test_add_enforces_policy_before_creating_anything() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), POLICY).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "fixstuff"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(25));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("must start with one of: feature/, fix/"),
+        "stderr: {stderr}"
+    );
+    assert!(!repo.join(".bonsai").exists());
+    assert_eq!(run_git(&repo, &["branch", "--list", "fixstuff"]), "");
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/ABC-123-login"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_add_suggests_a_conforming_name() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), POLICY).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args([
+            "--error-format",
+            "json",
+            "add",
+            "-c",
+            "feature-ABC-1-login page",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(25));
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["kind"], "invalid_branch_name");
+    assert_eq!(error["details"]["suggestion"], "feature/ABC-1-login-page");
+}
+
+#[test]
+fn test_git_rules_apply_without_a_policy() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/a..b"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(25));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("git does not allow it as a branch name"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        stderr.contains("hint: try 'feature/a.b'"),
+        "stderr: {stderr}"
+    );
+    assert_eq!(std::fs::read_dir(repo.join(".bonsai")).unwrap().count(), 0);
+}
+
+#[test]
+fn test_rename_enforces_policy() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), POLICY).unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/ABC-1-x"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args([
+            "rename",
+            "feature/ABC-1-x",
+            "fix/ABC-1-a-much-longer-description-than-allowed",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(25));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("the limit is 40"), "stderr: {stderr}");
+    assert!(repo.join(".bonsai/feature-ABC-1-x").is_dir());
+    run_git(&repo, &["rev-parse", "--verify", "feature/ABC-1-x"]);
+}
+
+#[test]
+fn test_invalid_policy_pattern_is_a_config_error() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(
+        repo.join(".bonsai.toml"),
+        "version = 2\n\n[branches]\npattern = '^feature/(['\n",
+    )
+    .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "validate"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid `branches.pattern`"),
+        "stderr: {stderr}"
+    );
+}
@@ -0,0 +1,139 @@
+use crate::helpers::*;
+
+const PROFILES: &str = r#"version = 2
+
+[[hooks.post_create]]
+type = "command"
+command = "touch base-hook"
+
+[[profiles]]
+name = "release"
+match = "release/*"
+
+[profiles.defaults]
+sync_strategy = "rebase"
+
+[profiles.hooks]
+merge = "replace"
+
+[[profiles.hooks.post_create]]
+type = "command"
+command = "touch release-hook"
+
+[[profiles]]
+name = "signed"
+
+[[profiles.hooks.post_create]]
+type = "command"
+command = "touch signed-hook"
+"#;
+
+#[test]
+fn test_matching_profile_replaces_hooks() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), PROFILES).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "release/1.0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let wt = repo.join(".bonsai/release-1.0");
+    assert!(wt.join("release-hook").exists());
+    assert!(!wt.join("base-hook").exists());
+
+    // Other branches keep the top-level hooks
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x"])
+        .output()
+        .unwrap();
+    let wt = repo.join(".bonsai/feature-x");
+    assert!(wt.join("base-hook").exists());
+    assert!(!wt.join("release-hook").exists());
+}
+
+#[test]
+fn test_explicit_profile_extends_hooks_and_is_remembered() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), PROFILES).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x", "--profile", "signed"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let wt = repo.join(".bonsai/feature-x");
+    assert!(wt.join("base-hook").exists());
+    assert!(wt.join("signed-hook").exists());
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "show", "--for", "feature/x"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("# feature/x: profile `signed`\n"),
+        "show output: {stdout}"
+    );
+    assert!(!stdout.contains("[[profiles]]"), "show output: {stdout}");
+}
+
+#[test]
+fn test_show_for_branch_applies_overrides() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), PROFILES).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "show", "--for", "release/2.0"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("sync_strategy = \"rebase\""),
+        "show output: {stdout}"
+    );
+    assert!(
+        stdout.contains("touch release-hook"),
+        "show output: {stdout}"
+    );
+    assert!(!stdout.contains("touch base-hook"), "show output: {stdout}");
+
+    let output = bonsai_cmd(&repo)
+        .args(["config", "show", "--for", "main"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("# main: no profile\n"),
+        "show output: {stdout}"
+    );
+}
+
+#[test]
+fn test_unknown_and_duplicate_profiles_are_errors() {
+    let (_tmp, repo) = setup_repo();
+    std::fs::write(repo.join(".bonsai.toml"), PROFILES).unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/x", "--profile", "nope"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    assert!(!repo.join(".bonsai/feature-x").exists());
+
+    std::fs::write(
+        repo.join(".bonsai.toml"),
+        format!("{PROFILES}\n[[profiles]]\nname = \"release\"\n"),
+    )
+    .unwrap();
+    let output = bonsai_cmd(&repo)
+        .args(["config", "validate"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(13));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("profile `release` is defined more than once"),
+        "stderr: {stderr}"
+    );
+}
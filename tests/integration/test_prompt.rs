@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use crate::helpers::*;
+
+fn prompt(dir: &Path, args: &[&str]) -> String {
+    let output = bonsai_cmd(dir).arg("prompt").args(args).output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn test_prompt_shows_linked_worktree_only() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/prompt"])
+        .output()
+        .unwrap();
+
+    assert_eq!(prompt(&repo, &[]), "");
+
+    let sub = repo.join(".bonsai/feature-prompt/nested");
+    std::fs::create_dir_all(&sub).unwrap();
+    assert_eq!(prompt(&sub, &["--no-status"]), "feature/prompt");
+}
+
+#[test]
+fn test_prompt_status_and_lock() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    let config = repo.join(".bonsai.toml");
+    let mut content = std::fs::read_to_string(&config).unwrap();
+    content.push_str("\n[prompt]\ntemplate = \"<{dir}> {status} {lock}\"\nstatus_ttl = 0\n");
+    std::fs::write(&config, content).unwrap();
+
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/dirty"])
+        .output()
+        .unwrap();
+    let wt = repo.join(".bonsai/feature-dirty");
+
+    assert_eq!(prompt(&wt, &[]), "<feature-dirty>");
+
+    std::fs::write(wt.join("new.txt"), "x").unwrap();
+    assert_eq!(prompt(&wt, &[]), "<feature-dirty> 1?");
+
+    bonsai_cmd(&repo)
+        .args(["lock", "feature/dirty"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        prompt(&wt, &["--format", "{name}{lock}"]),
+        "feature/dirty🔒"
+    );
+}
+
+#[test]
+fn test_shell_init_prompt_hook() {
+    let (_tmp, repo) = setup_repo();
+
+    let output = bonsai_cmd(&repo)
+        .args(["shell-init", "bash", "--prompt"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("__bonsai_prompt"));
+
+    let output = bonsai_cmd(&repo)
+        .args(["shell-init", "bash"])
+        .output()
+        .unwrap();
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("__bonsai_prompt"));
+}
@@ -0,0 +1,47 @@
+use crate::helpers::*;
+
+#[test]
+fn test_prune_merged_into_default_branch() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/merged"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["prune", "--merged", "--yes"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "prune failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(!repo.join(".bonsai/feature-merged").exists());
+}
+
+#[test]
+fn test_prune_merged_uses_init_default_branch() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    run_git(&repo, &["branch", "-m", "main", "develop"]);
+    run_git(&repo, &["config", "init.defaultBranch", "develop"]);
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/unmerged"])
+        .output()
+        .unwrap();
+
+    // Give the feature a commit that is not on develop
+    let wt = repo.join(".bonsai/feature-unmerged");
+    run_git(&wt, &["commit", "--allow-empty", "-m", "feature work"]);
+
+    let output = bonsai_cmd(&repo)
+        .args(["prune", "--merged", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(wt.exists());
+}
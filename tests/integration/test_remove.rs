@@ -52,3 +52,100 @@ fn test_remove_nonexistent_fails() {
         .unwrap();
     assert!(!output.status.success());
 }
+
+#[test]
+fn test_remove_refuses_to_lose_untracked_files() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/untracked"])
+        .output()
+        .unwrap();
+
+    let wt = repo.join(".bonsai/feature-untracked");
+    std::fs::write(wt.join("notes.txt"), "important\n").unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "feature/untracked", "--force"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(30));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("untracked files"), "{stderr}");
+    assert!(stderr.contains("notes.txt"));
+    assert!(stderr.contains("--discard-unpushed"), "{stderr}");
+    assert!(wt.exists());
+
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "feature/untracked", "--discard-unpushed"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!wt.exists());
+}
+
+#[test]
+fn test_remove_with_branch_refuses_to_lose_unpushed_commits() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/unpushed"])
+        .output()
+        .unwrap();
+
+    let wt = repo.join(".bonsai/feature-unpushed");
+    run_git(&wt, &["commit", "--allow-empty", "-m", "local only work"]);
+
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "feature/unpushed", "--with-branch", "--force"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("commits not on main"), "{stderr}");
+    assert!(stderr.contains("local only work"));
+    assert!(run_git(&repo, &["branch"]).contains("feature/unpushed"));
+
+    // Keeping the branch keeps the commits, so this is safe
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "feature/unpushed"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "remove failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_remove_refuses_to_lose_protected_ignored_files() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    let config = std::fs::read_to_string(repo.join(".bonsai.toml")).unwrap();
+    let config = config.replace(
+        "[defaults]\n",
+        "[defaults]\nprotected_files = [\".env*\"]\n",
+    );
+    std::fs::write(repo.join(".bonsai.toml"), config).unwrap();
+    std::fs::write(repo.join(".gitignore"), ".bonsai/\n.env*\n").unwrap();
+    run_git(&repo, &["add", ".gitignore"]);
+    run_git(&repo, &["commit", "-m", "ignore env"]);
+
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/env"])
+        .output()
+        .unwrap();
+    let wt = repo.join(".bonsai/feature-env");
+    std::fs::write(wt.join(".env.local"), "SECRET=1\n").unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "feature/env"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("protected ignored files"), "{stderr}");
+    assert!(stderr.contains(".env.local"));
+}
@@ -0,0 +1,169 @@
+use std::path::Path;
+
+use serde_json::Value;
+use tempfile::TempDir;
+
+use crate::helpers::*;
+
+/// A config that sets every field, so each one is compared with the schema.
+const FULL_CONFIG: &str = r#"
+version = 2
+
+[defaults]
+worktree_dir = ".bonsai"
+base_branch = "main"
+protected_files = [".env*"]
+sync_strategy = "rebase"
+
+[[hooks.post_create]]
+type = "copy"
+from = ".env"
+to = ".env"
+
+[[hooks.post_create]]
+type = "symlink"
+from = "node_modules"
+to = "node_modules"
+
+[[hooks.post_create]]
+type = "command"
+command = "npm install"
+env = { CI = "1" }
+
+[prompt]
+template = "{name}"
+status_ttl = 10
+
+[clone]
+branches = ["main"]
+
+[branches]
+pattern = '^[a-z]+/[a-z0-9-]+$'
+prefixes = ["feature/", "fix/"]
+max_length = 60
+lowercase = true
+template = "{type}/{issue}-{slug}"
+default_type = "feature"
+
+[[profiles]]
+name = "release"
+match = "release/*"
+
+[profiles.defaults]
+base_branch = "main"
+protected_files = ["signing.key"]
+sync_strategy = "fast-forward"
+
+[profiles.hooks]
+merge = "replace"
+
+[[profiles.hooks.post_create]]
+type = "command"
+command = "make release"
+env = { SIGN = "1" }
+"#;
+
+fn schema() -> Value {
+    let tmp = TempDir::new().unwrap();
+    let output = bonsai_cmd(tmp.path())
+        .args(["config", "schema"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_schema_matches_committed_file() {
+    let committed: Value =
+        serde_json::from_str(include_str!("../../schema/bonsai.schema.json")).unwrap();
+    assert!(
+        schema() == committed,
+        "schema/bonsai.schema.json is out of date; run `just schema`"
+    );
+}
+
+#[test]
+fn test_schema_matches_serde_structs() {
+    let config = bonsai::config::Config::parse(FULL_CONFIG, Path::new("full.toml")).unwrap();
+    let value = serde_json::to_value(&config).unwrap();
+    let schema = schema();
+    check(&value, &schema, &schema, "");
+}
+
+/// Every key serde writes is in the schema and every property in the schema
+/// is written, following `$ref`s and picking the `oneOf` branch by `type`.
+fn check(value: &Value, schema: &Value, root: &Value, path: &str) {
+    let schema = resolve(schema, root);
+
+    if let Some(branches) = schema.get("oneOf").and_then(Value::as_array) {
+        let branch = branches
+            .iter()
+            .map(|b| resolve(b, root))
+            .find(|b| match value.get("type") {
+                Some(tag) => b.pointer("/properties/type/const") == Some(tag),
+                None => b.get("const") == Some(value),
+            })
+            .unwrap_or_else(|| panic!("{path}: no schema branch for {value}"));
+        return check(value, branch, root, path);
+    }
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let additional = schema.get("additionalProperties").filter(|a| a.is_object());
+            for (key, child) in map {
+                let child_path = format!("{path}.{key}");
+                match (properties.and_then(|p| p.get(key)), additional) {
+                    (Some(child_schema), _) | (None, Some(child_schema)) => {
+                        check(child, child_schema, root, &child_path)
+                    }
+                    (None, None) => panic!("{child_path} is serialized but not in the schema"),
+                }
+            }
+            for key in properties.into_iter().flat_map(|p| p.keys()) {
+                assert!(
+                    map.contains_key(key),
+                    "{path}.{key} is in the schema but FULL_CONFIG does not set it"
+                );
+            }
+        }
+        Value::Array(items) => {
+            let item_schema = schema
+                .get("items")
+                .unwrap_or_else(|| panic!("{path}: array without item schema"));
+            for (i, item) in items.iter().enumerate() {
+                check(item, item_schema, root, &format!("{path}[{i}]"));
+            }
+        }
+        Value::String(s) => {
+            if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+                assert!(
+                    allowed.iter().any(|a| a == s),
+                    "{path}: {s} is not allowed by the schema"
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let pointer = reference.trim_start_matches('#');
+        return resolve(root.pointer(pointer).expect("dangling $ref"), root);
+    }
+    match schema.get("allOf").and_then(Value::as_array) {
+        Some(all) if all.len() == 1 => return resolve(&all[0], root),
+        _ => {}
+    }
+    // Optional fields are `anyOf` the type and null; serde never writes the null
+    match schema.get("anyOf").and_then(Value::as_array) {
+        Some(any) => any
+            .iter()
+            .map(|b| resolve(b, root))
+            .find(|b| b.get("type") != Some(&Value::from("null")))
+            .unwrap_or(schema),
+        None => schema,
+    }
+}
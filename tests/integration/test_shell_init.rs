@@ -1,5 +1,44 @@
+use std::path::Path;
+use std::process::Command;
+
 use crate::helpers::*;
 
+fn shell_init(repo: &Path, shell: &str) -> String {
+    let output = bonsai_cmd(repo)
+        .args(["shell-init", shell])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Parse `script` with the shell's own parser, without running it. Skipped
+/// when the shell is not on PATH; the dev shell provides all of them.
+fn assert_parses(script: &str, program: &str, args: &[&str]) {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let file = tmp.path().join("init");
+    std::fs::write(&file, script).unwrap();
+    let args: Vec<String> = args
+        .iter()
+        .map(|a| a.replace("{file}", file.to_str().unwrap()))
+        .collect();
+
+    let output = match Command::new(program).args(&args).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("{program} not installed; skipping parse check");
+            return;
+        }
+        Err(e) => panic!("cannot run {program}: {e}"),
+    };
+    assert!(
+        output.status.success(),
+        "{program} failed to parse script: {}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
 #[test]
 fn test_shell_init_bash() {
     let (_tmp, repo) = setup_repo();
@@ -12,6 +51,7 @@ fn test_shell_init_bash() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("bonsai()"));
     assert!(stdout.contains("bn()"));
+    assert_parses(&stdout, "bash", &["-n", "{file}"]);
 }
 
 #[test]
@@ -27,6 +67,12 @@ fn test_shell_init_zsh() {
     assert!(stdout.contains("bonsai()"));
 }
 
+#[test]
+fn test_shell_init_zsh_parses() {
+    let (_tmp, repo) = setup_repo();
+    assert_parses(&shell_init(&repo, "zsh"), "zsh", &["-n", "{file}"]);
+}
+
 #[test]
 fn test_shell_init_fish() {
     let (_tmp, repo) = setup_repo();
@@ -40,6 +86,86 @@ fn test_shell_init_fish() {
     assert!(stdout.contains("function bonsai"));
 }
 
+#[test]
+fn test_shell_init_fish_parses() {
+    let (_tmp, repo) = setup_repo();
+    assert_parses(
+        &shell_init(&repo, "fish"),
+        "fish",
+        &["--no-execute", "{file}"],
+    );
+}
+
+#[test]
+fn test_shell_init_powershell() {
+    let (_tmp, repo) = setup_repo();
+
+    let script = shell_init(&repo, "powershell");
+    assert!(script.contains("function bonsai"));
+    assert!(script.contains("function bn"));
+    assert!(script.contains("Register-ArgumentCompleter"));
+    // The wrapper keeps bonsai's exit code after following a directive
+    assert!(script.contains("$global:LASTEXITCODE = $ret"));
+    assert_eq!(shell_init(&repo, "pwsh"), script);
+}
+
+#[test]
+fn test_shell_init_powershell_parses() {
+    let (_tmp, repo) = setup_repo();
+    assert_parses(
+        &shell_init(&repo, "powershell"),
+        "pwsh",
+        &[
+            "-NoProfile",
+            "-Command",
+            "$errors = $null; \
+             [void][System.Management.Automation.Language.Parser]::ParseFile('{file}', [ref]$null, [ref]$errors); \
+             if ($errors) { $errors; exit 1 }",
+        ],
+    );
+}
+
+#[test]
+fn test_shell_init_elvish() {
+    let (_tmp, repo) = setup_repo();
+
+    let script = shell_init(&repo, "elvish");
+    assert!(script.contains("fn bonsai"));
+    assert!(script.contains("fn bn"));
+    assert!(script.contains("edit:completion:arg-completer[bonsai]"));
+}
+
+#[test]
+fn test_shell_init_elvish_parses() {
+    let (_tmp, repo) = setup_repo();
+    assert_parses(
+        &shell_init(&repo, "elvish"),
+        "elvish",
+        &["-compileonly", "{file}"],
+    );
+}
+
+#[test]
+fn test_shell_init_nushell() {
+    let (_tmp, repo) = setup_repo();
+
+    let script = shell_init(&repo, "nushell");
+    assert!(script.contains("def --env --wrapped bonsai"));
+    assert!(script.contains("def --env --wrapped bn"));
+    assert!(script.contains("nu-complete bonsai"));
+    assert_eq!(shell_init(&repo, "nu"), script);
+}
+
+#[test]
+fn test_shell_init_nushell_parses() {
+    let (_tmp, repo) = setup_repo();
+    assert_parses(
+        &shell_init(&repo, "nushell"),
+        "nu",
+        &["-c", "nu-check {file}"],
+    );
+}
+
 #[test]
 fn test_completion_bash() {
     let (_tmp, repo) = setup_repo();
@@ -52,3 +178,68 @@ fn test_completion_bash() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(!stdout.is_empty());
 }
+
+/// Ask the binary for completions the way the fish registration does.
+fn complete(repo: &Path, words: &[&str]) -> Vec<String> {
+    let output = bonsai_cmd(repo)
+        .env("COMPLETE", "fish")
+        .arg("--")
+        .args(words)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "completion failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.split('\t').next().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn test_shell_init_completes_every_subcommand() {
+    use clap::CommandFactory;
+
+    let (_tmp, repo) = setup_repo();
+
+    let candidates = complete(&repo, &["bonsai", ""]);
+    for sub in bonsai::cli::Cli::command().get_subcommands() {
+        assert!(
+            candidates.iter().any(|c| c == sub.get_name()),
+            "{} missing from completions: {candidates:?}",
+            sub.get_name()
+        );
+    }
+
+    // No shell carries its own copy of the command list
+    for shell in ["bash", "zsh", "fish", "powershell", "elvish", "nushell"] {
+        let script = shell_init(&repo, shell);
+        assert!(script.contains("COMPLETE"), "{shell}: {script}");
+        assert!(!script.contains("shell-init"), "{shell}: {script}");
+    }
+}
+
+#[test]
+fn test_shell_init_completes_worktrees_and_branches() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/complete-me"])
+        .output()
+        .unwrap();
+
+    let worktrees = complete(&repo, &["bn", "cd", ""]);
+    assert!(worktrees.contains(&"@".to_string()), "{worktrees:?}");
+    assert!(
+        worktrees.contains(&"feature/complete-me".to_string()),
+        "{worktrees:?}"
+    );
+
+    let branches = complete(&repo, &["bonsai", "add", "new-branch", "--base", ""]);
+    assert!(branches.contains(&"main".to_string()), "{branches:?}");
+
+    let flags = complete(&repo, &["bonsai", "remove", "--"]);
+    assert!(flags.contains(&"--with-branch".to_string()), "{flags:?}");
+}
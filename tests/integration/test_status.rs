@@ -28,3 +28,91 @@ fn test_status_specific_worktree() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("feature/status-test"));
 }
+
+#[test]
+fn test_status_shows_divergence_from_base() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/diverged"])
+        .output()
+        .unwrap();
+
+    let wt = repo.join(".bonsai/feature-diverged");
+    std::fs::write(wt.join("file.txt"), "one\ntwo\n").unwrap();
+    run_git(&wt, &["add", "file.txt"]);
+    run_git(&wt, &["commit", "-m", "feature work"]);
+    run_git(&repo, &["commit", "--allow-empty", "-m", "main moved"]);
+
+    let output = bonsai_cmd(&repo)
+        .args(["status", "feature/diverged"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Base: main ⇡1 ⇣1 1f +2 -0"), "{stdout}");
+    assert!(stdout.contains("Base moved: 1 new commit(s) on main"));
+}
+
+#[test]
+fn test_status_shows_conflicts_and_in_progress_merge() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/conflict"])
+        .output()
+        .unwrap();
+
+    let wt = repo.join(".bonsai/feature-conflict");
+    std::fs::write(wt.join("file.txt"), "feature\n").unwrap();
+    run_git(&wt, &["add", "file.txt"]);
+    run_git(&wt, &["commit", "-m", "feature side"]);
+
+    std::fs::write(repo.join("file.txt"), "main\n").unwrap();
+    run_git(&repo, &["add", "file.txt"]);
+    run_git(&repo, &["commit", "-m", "main side"]);
+
+    // Leave the merge half-done with a conflict
+    let merge = std::process::Command::new("git")
+        .args(["merge", "main"])
+        .current_dir(&wt)
+        .output()
+        .unwrap();
+    assert!(!merge.status.success());
+
+    let output = bonsai_cmd(&repo)
+        .args(["status", "feature/conflict"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Status: MERGE 1U"), "{stdout}");
+    assert!(stdout.contains("In progress: merge"));
+    assert!(stdout.contains("Conflicts: 1"));
+}
+
+#[test]
+fn test_status_counts_stashes_per_worktree() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/stash"])
+        .output()
+        .unwrap();
+
+    let wt = repo.join(".bonsai/feature-stash");
+    std::fs::write(wt.join("file.txt"), "work\n").unwrap();
+    run_git(&wt, &["add", "file.txt"]);
+    run_git(&wt, &["stash", "push", "-m", "parked"]);
+
+    let output = bonsai_cmd(&repo)
+        .args(["status", "feature/stash"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Stashes: 1"), "{stdout}");
+
+    let output = bonsai_cmd(&repo).args(["status", "main"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Stashes:"), "{stdout}");
+}
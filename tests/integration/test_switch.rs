@@ -0,0 +1,101 @@
+use crate::helpers::*;
+
+fn directive_file(tmp: &tempfile::TempDir) -> std::path::PathBuf {
+    let file = tmp.path().join("directive");
+    std::fs::write(&file, "").unwrap();
+    file
+}
+
+#[test]
+fn test_add_cd_writes_directive() {
+    let (tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    let file = directive_file(&tmp);
+
+    let output = bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/cd", "--cd"])
+        .env("BONSAI_DIRECTIVE_FILE", &file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let wt_path = std::fs::canonicalize(repo.join(".bonsai/feature-cd")).unwrap();
+    let directive = std::fs::read_to_string(&file).unwrap();
+    assert_eq!(directive, format!("cd {}\n", wt_path.display()));
+}
+
+#[test]
+fn test_switch_without_shell_integration_prints_path() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/switch"])
+        .output()
+        .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["switch", "feature/switch"])
+        .env_remove("BONSAI_DIRECTIVE_FILE")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.trim().ends_with(".bonsai/feature-switch"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("shell-init"));
+}
+
+#[test]
+fn test_remove_current_worktree_returns_to_main() {
+    let (tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/here"])
+        .output()
+        .unwrap();
+    let file = directive_file(&tmp);
+    let wt_path = repo.join(".bonsai/feature-here");
+
+    let output = bonsai_cmd(&wt_path)
+        .args(["remove", "feature/here"])
+        .env("BONSAI_DIRECTIVE_FILE", &file)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "remove failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let main_path = std::fs::canonicalize(&repo).unwrap();
+    let directive = std::fs::read_to_string(&file).unwrap();
+    assert_eq!(directive, format!("cd {}\n", main_path.display()));
+}
+
+#[test]
+fn test_move_current_worktree_follows_subdirectory() {
+    let (tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/away"])
+        .output()
+        .unwrap();
+    let file = directive_file(&tmp);
+    let sub = repo.join(".bonsai/feature-away/src");
+    std::fs::create_dir_all(&sub).unwrap();
+    let target = tmp.path().join("away");
+
+    let output = bonsai_cmd(&sub)
+        .args(["move", "feature/away", target.to_str().unwrap()])
+        .env("BONSAI_DIRECTIVE_FILE", &file)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "move failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let expected = std::fs::canonicalize(&target).unwrap().join("src");
+    let directive = std::fs::read_to_string(&file).unwrap();
+    assert_eq!(directive, format!("cd {}\n", expected.display()));
+}
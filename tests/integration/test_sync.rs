@@ -0,0 +1,127 @@
+use crate::helpers::*;
+
+#[test]
+fn test_sync_fast_forwards_onto_base() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/behind"])
+        .output()
+        .unwrap();
+    run_git(&repo, &["commit", "--allow-empty", "-m", "main moved"]);
+
+    let output = bonsai_cmd(&repo)
+        .args(["sync", "feature/behind"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "sync failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("updated"), "{stdout}");
+
+    let main = run_git(&repo, &["rev-parse", "main"]);
+    let feature = run_git(&repo, &["rev-parse", "feature/behind"]);
+    assert_eq!(feature, main);
+}
+
+#[test]
+fn test_sync_all_updates_from_upstream_and_skips_dirty() {
+    let (_tmp, origin) = setup_repo();
+    // Committed config keeps the clone's main free of untracked files
+    bonsai_cmd(&origin).args(["init"]).output().unwrap();
+    run_git(&origin, &["add", ".bonsai.toml", ".gitignore"]);
+    run_git(&origin, &["commit", "-m", "set up bonsai"]);
+    let clone_tmp = tempfile::TempDir::new().unwrap();
+    let repo = clone_tmp.path().join("clone");
+    run_git(
+        clone_tmp.path(),
+        &["clone", &origin.to_string_lossy(), "clone"],
+    );
+    run_git(&repo, &["config", "user.email", "test@test.com"]);
+    run_git(&repo, &["config", "user.name", "Test"]);
+
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/dirty"])
+        .output()
+        .unwrap();
+    std::fs::write(repo.join(".bonsai/feature-dirty/.gitignore"), "x\n").unwrap();
+    run_git(&repo.join(".bonsai/feature-dirty"), &["add", ".gitignore"]);
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/untracked"])
+        .output()
+        .unwrap();
+    std::fs::write(repo.join(".bonsai/feature-untracked/notes.txt"), "x\n").unwrap();
+
+    run_git(&origin, &["commit", "--allow-empty", "-m", "upstream work"]);
+
+    let output = bonsai_cmd(&repo).args(["sync", "--all"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "sync failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("uncommitted changes"), "{stdout}");
+    assert!(stdout.contains("untracked files"), "{stdout}");
+
+    let upstream = run_git(&origin, &["rev-parse", "main"]);
+    let local = run_git(&repo, &["rev-parse", "main"]);
+    assert_eq!(local, upstream);
+}
+
+#[test]
+fn test_sync_rebase_conflict_is_aborted() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/conflict"])
+        .output()
+        .unwrap();
+
+    let wt = repo.join(".bonsai/feature-conflict");
+    std::fs::write(wt.join("file.txt"), "feature\n").unwrap();
+    run_git(&wt, &["add", "file.txt"]);
+    run_git(&wt, &["commit", "-m", "feature side"]);
+    let before = run_git(&wt, &["rev-parse", "HEAD"]);
+
+    std::fs::write(repo.join("file.txt"), "main\n").unwrap();
+    run_git(&repo, &["add", "file.txt"]);
+    run_git(&repo, &["commit", "-m", "main side"]);
+
+    let output = bonsai_cmd(&repo)
+        .args(["sync", "--rebase", "feature/conflict"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("rebase aborted"), "{stdout}");
+
+    assert_eq!(run_git(&wt, &["rev-parse", "HEAD"]), before);
+    assert!(run_git(&wt, &["status", "--porcelain"]).is_empty());
+}
+
+#[test]
+fn test_sync_fails_when_status_is_unavailable() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/broken"])
+        .output()
+        .unwrap();
+    std::fs::write(
+        repo.join(".bonsai/feature-broken/.git"),
+        "gitdir: /nonexistent\n",
+    )
+    .unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["sync", "--no-fetch", "feature/broken"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(51));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("status unavailable"), "{stdout}");
+}
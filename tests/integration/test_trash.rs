@@ -0,0 +1,127 @@
+use crate::helpers::*;
+
+#[test]
+fn test_remove_trash_and_restore() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/trash"])
+        .output()
+        .unwrap();
+
+    let wt = repo.join(".bonsai/feature-trash");
+    run_git(&wt, &["commit", "--allow-empty", "-m", "unpushed work"]);
+    let head = run_git(&wt, &["rev-parse", "HEAD"]);
+    std::fs::write(wt.join("draft.txt"), "in progress\n").unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "feature/trash", "--with-branch", "--trash"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "remove failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!wt.exists());
+    assert!(!run_git(&repo, &["branch"]).contains("feature/trash"));
+
+    let output = bonsai_cmd(&repo).args(["trash", "list"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("feature/trash"), "{stdout}");
+
+    let output = bonsai_cmd(&repo)
+        .args(["restore", "feature/trash"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "restore failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(run_git(&repo, &["rev-parse", "feature/trash"]), head);
+    assert_eq!(
+        std::fs::read_to_string(wt.join("draft.txt")).unwrap(),
+        "in progress\n"
+    );
+    assert!(run_git(&repo, &["for-each-ref", "refs/bonsai/trash"]).is_empty());
+}
+
+#[test]
+fn test_trash_purge_older_than() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/purge"])
+        .output()
+        .unwrap();
+    bonsai_cmd(&repo)
+        .args(["remove", "feature/purge", "--trash"])
+        .output()
+        .unwrap();
+
+    // A fresh snapshot is younger than a day and survives
+    bonsai_cmd(&repo)
+        .args(["trash", "purge", "--older-than", "1d"])
+        .output()
+        .unwrap();
+    assert!(!run_git(&repo, &["for-each-ref", "refs/bonsai/trash"]).is_empty());
+
+    let output = bonsai_cmd(&repo).args(["trash", "purge"]).output().unwrap();
+    assert!(output.status.success());
+    assert!(run_git(&repo, &["for-each-ref", "refs/bonsai/trash"]).is_empty());
+
+    let output = bonsai_cmd(&repo).args(["trash", "list"]).output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Trash is empty."));
+}
+
+#[test]
+fn test_failed_trash_keeps_changes_in_worktree() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/trash"])
+        .output()
+        .unwrap();
+    let wt = repo.join(".bonsai/feature-trash");
+    std::fs::write(wt.join("draft.txt"), "in progress\n").unwrap();
+
+    // A file where the trash directory goes makes saving the entry fail
+    std::fs::create_dir_all(repo.join(".git/bonsai")).unwrap();
+    std::fs::write(repo.join(".git/bonsai/trash"), "").unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["remove", "feature/trash", "--trash"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(wt.join("draft.txt")).unwrap(),
+        "in progress\n"
+    );
+    assert_eq!(run_git(&repo, &["stash", "list"]), "");
+    assert!(run_git(&repo, &["for-each-ref", "refs/bonsai/trash"]).is_empty());
+}
+
+#[test]
+fn test_trashing_same_directory_twice_keeps_both() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+
+    for _ in 0..2 {
+        bonsai_cmd(&repo)
+            .args(["add", "-c", "feature/again"])
+            .output()
+            .unwrap();
+        let output = bonsai_cmd(&repo)
+            .args(["remove", "feature/again", "--with-branch", "--trash"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+
+    let output = bonsai_cmd(&repo).args(["trash", "list"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("feature/again").count(), 2, "{stdout}");
+}
@@ -0,0 +1,178 @@
+use crate::helpers::*;
+
+#[test]
+fn test_undo_rename() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/before"])
+        .output()
+        .unwrap();
+    bonsai_cmd(&repo)
+        .args(["rename", "feature/before", "feature/after"])
+        .output()
+        .unwrap();
+    assert!(repo.join(".bonsai/feature-after").is_dir());
+
+    let output = bonsai_cmd(&repo).args(["undo"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "undo failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let branches = run_git(&repo, &["branch"]);
+    assert!(branches.contains("feature/before"));
+    assert!(!branches.contains("feature/after"));
+    assert!(repo.join(".bonsai/feature-before").is_dir());
+    assert!(!repo.join(".bonsai/feature-after").exists());
+
+    // The journal entry is consumed
+    let output = bonsai_cmd(&repo).args(["undo"]).output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Nothing to undo."));
+}
+
+#[test]
+fn test_undo_remove_with_branch() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/undo-remove"])
+        .output()
+        .unwrap();
+    let head = run_git(&repo, &["rev-parse", "feature/undo-remove"]);
+
+    bonsai_cmd(&repo)
+        .args(["remove", "feature/undo-remove", "--with-branch"])
+        .output()
+        .unwrap();
+    assert!(!repo.join(".bonsai/feature-undo-remove").exists());
+
+    let output = bonsai_cmd(&repo).args(["undo"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "undo failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(repo.join(".bonsai/feature-undo-remove").is_dir());
+    assert_eq!(run_git(&repo, &["rev-parse", "feature/undo-remove"]), head);
+}
+
+#[test]
+fn test_rename_rolls_back_when_move_fails() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/stuck"])
+        .output()
+        .unwrap();
+
+    // Occupy the destination so `git worktree move` fails after the branch rename
+    std::fs::write(repo.join(".bonsai/feature-blocked"), "x").unwrap();
+
+    let output = bonsai_cmd(&repo)
+        .args(["rename", "feature/stuck", "feature/blocked"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("rolling back 1 step(s)"), "{stderr}");
+
+    let branches = run_git(&repo, &["branch"]);
+    assert!(branches.contains("feature/stuck"));
+    assert!(!branches.contains("feature/blocked"));
+}
+
+#[test]
+fn test_undo_asks_when_repository_changed() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/before"])
+        .output()
+        .unwrap();
+    bonsai_cmd(&repo)
+        .args(["rename", "feature/before", "feature/after"])
+        .output()
+        .unwrap();
+    // Someone reuses the old name by hand
+    run_git(&repo, &["branch", "feature/before"]);
+
+    let output = bonsai_cmd(&repo).args(["undo"]).output().unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("branch feature/before exists again"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("Aborted."), "{stderr}");
+    assert!(repo.join(".bonsai/feature-after").is_dir());
+    assert!(run_git(&repo, &["branch"]).contains("feature/after"));
+
+    // Nothing was consumed, so the operation can still be undone
+    run_git(&repo, &["branch", "-D", "feature/before"]);
+    let output = bonsai_cmd(&repo).args(["undo"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "undo failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(repo.join(".bonsai/feature-before").is_dir());
+}
+
+#[test]
+fn test_old_journals_are_pruned() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/before"])
+        .output()
+        .unwrap();
+    let journal = repo.join(".git/bonsai/journal");
+    std::fs::create_dir_all(&journal).unwrap();
+    for ts in 1..=25 {
+        std::fs::write(
+            journal.join(format!("{ts}.toml")),
+            "command = \"old\"\nstarted_at = 0\n",
+        )
+        .unwrap();
+    }
+
+    bonsai_cmd(&repo)
+        .args(["rename", "feature/before", "feature/after"])
+        .output()
+        .unwrap();
+    assert_eq!(std::fs::read_dir(&journal).unwrap().count(), 20);
+    assert!(!journal.join("1.toml").exists());
+}
+
+#[test]
+fn test_undo_move_to_relative_path_from_another_directory() {
+    let (_tmp, repo) = setup_repo();
+    bonsai_cmd(&repo).args(["init"]).output().unwrap();
+    bonsai_cmd(&repo)
+        .args(["add", "-c", "feature/moving"])
+        .output()
+        .unwrap();
+    let sub = repo.join("sub");
+    std::fs::create_dir(&sub).unwrap();
+
+    let output = bonsai_cmd(&sub)
+        .args(["move", "feature/moving", "moved"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "move failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(sub.join("moved").is_dir());
+
+    let output = bonsai_cmd(&repo).args(["undo"]).output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "undo failed: {stderr}");
+    assert!(!stderr.contains("has changed"), "{stderr}");
+    assert!(repo.join(".bonsai/feature-moving").is_dir());
+    assert!(!sub.join("moved").exists());
+}